@@ -0,0 +1,844 @@
+//! Implementations of the ppcli subcommands. Each prints its result with the
+//! given `Printer` and returns whether the planner was modified and needs to
+//! be saved.
+
+use std::fs;
+use std::io::{self, IsTerminal, Read};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
+use clap::ValueEnum;
+use crossterm::terminal;
+use pplib::{
+    validate_exercise_markdown, ExerciseDocument, Interleaving, PracticeExercise, PracticeReport,
+    PracticeSegment, RestBreaks, SchedulePlanner, SchedulerError, SegmentKind, SkillReport,
+};
+
+use crate::editor;
+use crate::output::{
+    user_error, ConfigOutput, ConfigValue, ExerciseOutput, HistoryEntry, HistorySkill,
+    ImportOutput, InterleavingOutput, LogOutput, OutputFormat, PracticeOutput, Printer,
+    RestBreaksOutput, ScheduleOutput, SegmentOutput, SkillOutput, SkillsOutput, StreakOutput,
+};
+use crate::render::render_exercise;
+use crate::session_file::SessionFile;
+use crate::settings::Hooks;
+use crate::tui::{self, PracticeOutcome};
+use crate::ConfigKey;
+
+fn segment_output(scheduler: &SchedulePlanner, kind: SegmentKind) -> Option<SegmentOutput> {
+    scheduler.segment(kind).map(|segment| SegmentOutput {
+        skill_name: segment.skill_name.clone(),
+        minutes: segment.practice_time.num_minutes(),
+    })
+}
+
+fn print_schedule(out: &Printer, scheduler: &SchedulePlanner) -> Result<()> {
+    let schedule =
+        ScheduleOutput {
+            sessions_completed_today: scheduler.todays_sessions(Utc::now()).count(),
+            skill_practice_minutes: scheduler.config.skill_practice_time.num_minutes(),
+            skills: scheduler
+                .get_todays_schedule()
+                .map(|skills| skills.iter().map(|s| s.skill_name.clone()).collect())
+                .unwrap_or_default(),
+            pinned: scheduler.todays_pinned.iter().cloned().collect(),
+            warm_up: segment_output(scheduler, SegmentKind::WarmUp),
+            cool_down: segment_output(scheduler, SegmentKind::CoolDown),
+            rest_breaks: scheduler.config.rest_breaks.as_ref().map(|rest_breaks| {
+                RestBreaksOutput {
+                    every_skills: rest_breaks.every_skills,
+                    minutes: rest_breaks.rest_time.num_minutes(),
+                }
+            }),
+            interleaving: scheduler.config.interleaving.as_ref().map(|interleaving| {
+                InterleavingOutput {
+                    rounds: interleaving.rounds,
+                    minutes: interleaving.block_time.num_minutes(),
+                }
+            }),
+        };
+
+    out.print(&schedule, |schedule| {
+        let per_skill = match &schedule.interleaving {
+            Some(interleaving) => format!(
+                "{} rounds of {} minutes per skill",
+                interleaving.rounds, interleaving.minutes
+            ),
+            None => format!("{} minutes per skill", schedule.skill_practice_minutes),
+        };
+        match schedule.sessions_completed_today {
+            0 => println!("Today's schedule ({}):", per_skill),
+            completed => println!("Today's session {} ({}):", completed + 1, per_skill),
+        }
+        if let Some(warm_up) = &schedule.warm_up {
+            println!("Warm-up: {} ({}m)", warm_up.skill_name, warm_up.minutes);
+        }
+        for (idx, skill) in schedule.skills.iter().enumerate() {
+            match schedule.pinned.contains(skill) {
+                true => println!("{}. {} (pinned)", idx + 1, skill),
+                false => println!("{}. {}", idx + 1, skill),
+            }
+        }
+        if let Some(cool_down) = &schedule.cool_down {
+            println!(
+                "Cool-down: {} ({}m)",
+                cool_down.skill_name, cool_down.minutes
+            );
+        }
+        if let Some(rest_breaks) = &schedule.rest_breaks {
+            println!(
+                "Resting {}m after every {} skills",
+                rest_breaks.minutes, rest_breaks.every_skills
+            );
+        }
+    })
+}
+
+pub fn today(out: &Printer, scheduler: &mut SchedulePlanner) -> Result<bool> {
+    let scheduled_at = scheduler.todays_schedule_time;
+    scheduler.update_todays_schedule(false, Utc::now())?;
+    print_schedule(out, scheduler)?;
+
+    Ok(scheduler.todays_schedule_time != scheduled_at)
+}
+
+/// Applies an edit to today's schedule, picking the schedule first if it
+/// hasn't been yet.
+pub fn edit_today(
+    out: &Printer,
+    scheduler: &mut SchedulePlanner,
+    edit: impl FnOnce(&mut SchedulePlanner, DateTime<Utc>) -> Result<(), SchedulerError>,
+) -> Result<bool> {
+    let now = Utc::now();
+    scheduler.update_todays_schedule(false, now)?;
+    edit(scheduler, now)?;
+    print_schedule(out, scheduler)?;
+
+    Ok(true)
+}
+
+/// Pinned skills stay in the schedule.
+pub fn shuffle(out: &Printer, scheduler: &mut SchedulePlanner) -> Result<bool> {
+    scheduler.update_todays_schedule(true, Utc::now())?;
+    print_schedule(out, scheduler)?;
+
+    Ok(true)
+}
+
+/// Offers to resume a session left behind by a previous run, which is
+/// discarded if declined.
+fn resume_saved_session(scheduler: &mut SchedulePlanner, session_file: &SessionFile) -> Result<()> {
+    let saved = match session_file.load() {
+        Ok(Some(saved)) => saved,
+        Ok(None) => return Ok(()),
+        Err(e) => {
+            eprintln!("Discarding the unfinished practice session: {:#}", e);
+            return session_file.remove();
+        }
+    };
+
+    let question = format!(
+        "Resume the practice session started {}, which stopped during {}?",
+        saved
+            .start_time()
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M"),
+        saved.current_skill_name()
+    );
+    if editor::confirm(&question, true)? {
+        scheduler.restore_session(saved)?;
+    } else {
+        session_file.remove()?;
+    }
+
+    Ok(())
+}
+
+pub fn practice(
+    out: &Printer,
+    scheduler: &mut SchedulePlanner,
+    hooks: &Hooks,
+    save_path: &Path,
+) -> Result<bool> {
+    let mut session_file = SessionFile::for_profile(save_path);
+    resume_saved_session(scheduler, &session_file)?;
+    let outcome = match tui::practice(scheduler, hooks, &mut session_file)? {
+        PracticeOutcome::Completed => "completed",
+        PracticeOutcome::Stopped => "stopped",
+    };
+    out.print(&PracticeOutput { outcome }, |practice| {
+        match practice.outcome {
+            "completed" => println!("Finished practicing for today!"),
+            _ => println!("Stopped practicing."),
+        }
+    })?;
+
+    Ok(true)
+}
+
+pub fn history(out: &Printer, scheduler: &SchedulePlanner, days: usize) -> Result<bool> {
+    let since = Utc::now() - Duration::days(days as i64);
+    let history = scheduler
+        .history
+        .range(since..)
+        .rev()
+        .map(|(time, record)| HistoryEntry {
+            time: *time,
+            skills: record
+                .skills
+                .iter()
+                .map(|s| HistorySkill {
+                    skill_name: s.skill_name.clone(),
+                    minutes: record.durations.get(&s.skill_name).map(|d| d.num_minutes()),
+                    note: record.notes.get(&s.skill_name).cloned(),
+                    skipped: record.skipped.contains(&s.skill_name),
+                })
+                .collect(),
+        })
+        .collect::<Vec<_>>();
+
+    out.print(&history, |history| {
+        for entry in history.iter() {
+            let skills = entry
+                .skills
+                .iter()
+                .map(|s| match (s.minutes, s.skipped) {
+                    (Some(minutes), true) => format!("{} ({}m, skipped)", s.skill_name, minutes),
+                    (Some(minutes), false) => format!("{} ({}m)", s.skill_name, minutes),
+                    (None, true) => format!("{} (skipped)", s.skill_name),
+                    (None, false) => s.skill_name.clone(),
+                })
+                .collect::<Vec<_>>();
+            println!(
+                "{}  {}",
+                entry.time.format("%Y-%m-%d %H:%M"),
+                skills.join(", ")
+            );
+            for skill in entry.skills.iter() {
+                if let Some(note) = &skill.note {
+                    println!("    {}: {}", skill.skill_name, note);
+                }
+            }
+        }
+
+        if history.is_empty() {
+            println!("No history");
+        }
+    })?;
+
+    Ok(false)
+}
+
+pub fn streak(out: &Printer, scheduler: &SchedulePlanner) -> Result<bool> {
+    let streak = StreakOutput {
+        streak: scheduler.get_streak(Utc::now()),
+    };
+    out.print(&streak, |streak| println!("Streak: {} days", streak.streak))?;
+
+    Ok(false)
+}
+
+fn format_minutes(minutes: i64) -> String {
+    if minutes < 60 {
+        return format!("{}m", minutes);
+    }
+
+    format!("{}h {:02}m", minutes / 60, minutes % 60)
+}
+
+fn format_last_practiced(skill: &SkillReport) -> String {
+    match skill.last_practiced {
+        Some(day) => day.to_string(),
+        None => "never".to_string(),
+    }
+}
+
+fn print_report_table(report: &PracticeReport) {
+    let days = (report.end - report.start).num_days() + 1;
+    println!("Practice from {} to {}", report.start, report.end);
+    println!("Days practiced: {} of {}", report.days_practiced, days);
+    println!("Total practice: {}", format_minutes(report.total_minutes));
+    println!("Current streak: {} days", report.current_streak);
+    println!("Longest streak: {} days", report.longest_streak);
+
+    let name_width = report
+        .skills
+        .iter()
+        .chain(report.neglected_skills.iter())
+        .map(|s| s.skill_name.len())
+        .max()
+        .unwrap_or(0)
+        .max("Skill".len());
+    if !report.skills.is_empty() {
+        println!();
+        println!(
+            "{:<width$}  {:>8}  {:>8}  Last practiced",
+            "Skill",
+            "Minutes",
+            "Sessions",
+            width = name_width
+        );
+        for skill in report.skills.iter() {
+            println!(
+                "{:<width$}  {:>8}  {:>8}  {}",
+                skill.skill_name,
+                skill.minutes,
+                skill.sessions,
+                format_last_practiced(skill),
+                width = name_width
+            );
+        }
+    }
+
+    if !report.neglected_skills.is_empty() {
+        println!();
+        println!("Neglected skills:");
+        for skill in report.neglected_skills.iter() {
+            let last_practiced = match skill.last_practiced {
+                Some(day) => format!("last practiced {}", day),
+                None => "never practiced".to_string(),
+            };
+            println!(
+                "{:<width$}  {}",
+                skill.skill_name,
+                last_practiced,
+                width = name_width
+            );
+        }
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if !field.contains(&[',', '"', '\n'][..]) {
+        return field.to_string();
+    }
+
+    format!("\"{}\"", field.replace('"', "\"\""))
+}
+
+/// One row per skill, including the neglected ones.
+fn print_report_csv(report: &PracticeReport) {
+    println!("skill,minutes,sessions,last_practiced");
+    for skill in report.skills.iter().chain(report.neglected_skills.iter()) {
+        println!(
+            "{},{},{},{}",
+            csv_field(&skill.skill_name),
+            skill.minutes,
+            skill.sessions,
+            skill
+                .last_practiced
+                .map(|day| day.to_string())
+                .unwrap_or_default()
+        );
+    }
+}
+
+pub fn report(
+    out: &Printer,
+    scheduler: &SchedulePlanner,
+    month: bool,
+    range: Option<&[NaiveDate]>,
+) -> Result<bool> {
+    let now = Utc::now();
+    let today = now.date().naive_utc();
+    let (start, end) = match range {
+        Some([start, end]) => (*start, *end),
+        Some(_) => unreachable!("clap requires two dates for the range"),
+        None if month => (today - Duration::days(29), today),
+        None => (today - Duration::days(6), today),
+    };
+    if start > end {
+        return Err(user_error("The range must start before it ends"));
+    }
+
+    let report = scheduler.report(start, end, now);
+    match out.format {
+        OutputFormat::Csv => print_report_csv(&report),
+        _ => out.print(&report, print_report_table)?,
+    }
+
+    Ok(false)
+}
+
+/// Parses a NAME or NAME=MINUTES argument of the log command.
+fn parse_skill_duration(scheduler: &SchedulePlanner, arg: &str) -> Result<(String, Duration)> {
+    match arg.rsplit_once('=') {
+        Some((name, minutes)) => {
+            let minutes: i64 = minutes
+                .trim()
+                .parse()
+                .map_err(|_| user_error(format!("Minutes for {} must be a whole number", name)))?;
+            Ok((name.trim().to_string(), Duration::minutes(minutes)))
+        }
+        None => Ok((arg.to_string(), scheduler.config.skill_practice_time)),
+    }
+}
+
+pub fn log(
+    out: &Printer,
+    scheduler: &mut SchedulePlanner,
+    skills: &[String],
+    date: Option<NaiveDate>,
+) -> Result<bool> {
+    let now = Utc::now();
+    let time = match date {
+        Some(date) if date != now.date().naive_utc() => scheduler.past_log_time(date),
+        _ => now,
+    };
+    let skill_durations = skills
+        .iter()
+        .map(|arg| parse_skill_duration(scheduler, arg))
+        .collect::<Result<Vec<_>>>()?;
+
+    scheduler.log_practice(time, &skill_durations, now)?;
+    let logged = LogOutput {
+        time,
+        streak: scheduler.get_streak(now),
+    };
+    out.print(&logged, |logged| {
+        println!("Logged practice on {}", logged.time.format("%Y-%m-%d"));
+        println!("Streak: {} days", logged.streak);
+    })?;
+
+    Ok(true)
+}
+
+pub fn list_skills(out: &Printer, scheduler: &SchedulePlanner) -> Result<bool> {
+    let skills = SkillsOutput {
+        skills: scheduler
+            .config
+            .skills
+            .iter()
+            .map(|skill| SkillOutput {
+                skill_name: skill.skill_name.clone(),
+                exercises: skill
+                    .exercises
+                    .iter()
+                    .map(|e| e.exercise_name.clone())
+                    .collect(),
+            })
+            .collect(),
+        archived_skills: scheduler
+            .config
+            .archived_skills
+            .iter()
+            .map(|s| s.skill_name.clone())
+            .collect(),
+    };
+
+    out.print(&skills, |skills| {
+        for skill in skills.skills.iter() {
+            println!("{}", skill.skill_name);
+            for exercise in skill.exercises.iter() {
+                println!("    {}", exercise);
+            }
+        }
+
+        if !skills.archived_skills.is_empty() {
+            println!();
+            println!("Trash:");
+            for skill in skills.archived_skills.iter() {
+                println!("{}", skill);
+            }
+        }
+    })?;
+
+    Ok(false)
+}
+
+pub fn add_skill(out: &Printer, scheduler: &mut SchedulePlanner, name: &str) -> Result<bool> {
+    scheduler.add_skill(name)?;
+    out.done(format!("Added skill: {}", name))?;
+
+    Ok(true)
+}
+
+pub fn rename_skill(
+    out: &Printer,
+    scheduler: &mut SchedulePlanner,
+    name: &str,
+    new_name: &str,
+) -> Result<bool> {
+    scheduler.rename_skill(name, new_name)?;
+    out.done(format!("Renamed skill {} to {}", name, new_name))?;
+
+    Ok(true)
+}
+
+pub fn remove_skill(out: &Printer, scheduler: &mut SchedulePlanner, name: &str) -> Result<bool> {
+    scheduler.archive_skill(name)?;
+    out.done(format!("Moved skill to the trash: {}", name))?;
+
+    Ok(true)
+}
+
+pub fn restore_skill(out: &Printer, scheduler: &mut SchedulePlanner, name: &str) -> Result<bool> {
+    scheduler.restore_skill(name)?;
+    out.done(format!("Restored skill: {}", name))?;
+
+    Ok(true)
+}
+
+pub fn set_segment(
+    out: &Printer,
+    scheduler: &mut SchedulePlanner,
+    kind: SegmentKind,
+    skill: &str,
+    minutes: usize,
+) -> Result<bool> {
+    let segment = PracticeSegment {
+        skill_name: skill.to_string(),
+        practice_time: Duration::minutes(minutes as i64),
+    };
+    scheduler.set_segment(kind, Some(segment))?;
+    out.done(format!(
+        "Set the {} to {} minutes of {}",
+        kind.name(),
+        minutes,
+        skill
+    ))?;
+
+    Ok(true)
+}
+
+pub fn clear_segment(
+    out: &Printer,
+    scheduler: &mut SchedulePlanner,
+    kind: SegmentKind,
+) -> Result<bool> {
+    if scheduler.segment(kind).is_none() {
+        out.done(format!("There is no {} to remove", kind.name()))?;
+        return Ok(false);
+    }
+
+    scheduler.set_segment(kind, None)?;
+    out.done(format!("Removed the {}", kind.name()))?;
+
+    Ok(true)
+}
+
+pub fn set_rest_breaks(
+    out: &Printer,
+    scheduler: &mut SchedulePlanner,
+    every: usize,
+    minutes: usize,
+) -> Result<bool> {
+    scheduler.set_rest_breaks(Some(RestBreaks {
+        every_skills: every,
+        rest_time: Duration::minutes(minutes as i64),
+    }))?;
+    out.done(format!(
+        "Resting {} minutes after every {} skills",
+        minutes, every
+    ))?;
+
+    Ok(true)
+}
+
+pub fn set_interleaving(
+    out: &Printer,
+    scheduler: &mut SchedulePlanner,
+    rounds: usize,
+    minutes: usize,
+) -> Result<bool> {
+    scheduler.set_interleaving(Some(Interleaving {
+        rounds,
+        block_time: Duration::minutes(minutes as i64),
+    }))?;
+    out.done(format!(
+        "Practicing the skills in {} rounds of {} minutes each",
+        rounds, minutes
+    ))?;
+
+    Ok(true)
+}
+
+pub fn clear_interleaving(out: &Printer, scheduler: &mut SchedulePlanner) -> Result<bool> {
+    if scheduler.config.interleaving.is_none() {
+        out.done("Interleaving is already off".to_string())?;
+        return Ok(false);
+    }
+
+    scheduler.set_interleaving(None)?;
+    out.done("Turned interleaving off".to_string())?;
+
+    Ok(true)
+}
+
+pub fn clear_rest_breaks(out: &Printer, scheduler: &mut SchedulePlanner) -> Result<bool> {
+    if scheduler.config.rest_breaks.is_none() {
+        out.done("Rest breaks are already off".to_string())?;
+        return Ok(false);
+    }
+
+    scheduler.set_rest_breaks(None)?;
+    out.done("Turned rest breaks off".to_string())?;
+
+    Ok(true)
+}
+
+/// Reads `file`, or stdin if no file is given.
+fn read_input(file: Option<&Path>) -> Result<String> {
+    match file {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|e| user_error(format!("Unable to read {}: {}", path.display(), e))),
+        None => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .context("Unable to read stdin")?;
+            Ok(input)
+        }
+    }
+}
+
+pub fn add_exercise(
+    out: &Printer,
+    scheduler: &mut SchedulePlanner,
+    skill: &str,
+    name: &str,
+    file: Option<&Path>,
+) -> Result<bool> {
+    let markdown = read_input(file)?;
+    scheduler.add_exercise(skill, name, &markdown)?;
+    out.done(format!("Added exercise {} to {}", name, skill))?;
+
+    Ok(true)
+}
+
+pub fn show_exercise(
+    out: &Printer,
+    scheduler: &SchedulePlanner,
+    skill: &str,
+    exercise: &str,
+) -> Result<bool> {
+    let exercise = scheduler.get_exercise(skill, exercise)?;
+    let exercise = ExerciseOutput {
+        skill_name: skill.to_string(),
+        exercise_name: exercise.exercise_name.clone(),
+        markdown: exercise.exercise_markdown_contents.clone(),
+    };
+
+    out.print(&exercise, |exercise| {
+        // only style and wrap the output when it's shown in a terminal
+        let (width, styled) = match terminal::size() {
+            Ok((cols, _)) if io::stdout().is_terminal() => (cols as usize, true),
+            _ => (80, false),
+        };
+        for line in render_exercise(&exercise.markdown, width, styled) {
+            println!("{}", line);
+        }
+    })?;
+
+    Ok(false)
+}
+
+pub fn edit_exercise(
+    out: &Printer,
+    scheduler: &mut SchedulePlanner,
+    skill: &str,
+    exercise: &str,
+    file: Option<&Path>,
+) -> Result<bool> {
+    // make sure the exercise exists before waiting on input
+    let current = scheduler.get_exercise(skill, exercise)?;
+    if file.is_none() && io::stdin().is_terminal() {
+        return edit_exercise_in_editor(out, scheduler, skill, &current);
+    }
+
+    let markdown = read_input(file)?;
+    validate_exercise_markdown(&markdown)?;
+    scheduler.update_exercise(skill, exercise, &markdown)?;
+    out.done(format!("Updated exercise {} of {}", exercise, skill))?;
+
+    Ok(true)
+}
+
+/// Opens the exercise in the user's editor until it's saved with valid front
+/// matter and vextab, or the user gives up.
+fn edit_exercise_in_editor(
+    out: &Printer,
+    scheduler: &mut SchedulePlanner,
+    skill: &str,
+    exercise: &PracticeExercise,
+) -> Result<bool> {
+    let original = ExerciseDocument::from_exercise(exercise);
+    let mut text = original.to_text();
+    let edited = loop {
+        text = editor::edit(&text, "md")?;
+        match ExerciseDocument::parse(&text) {
+            Ok(edited) => break edited,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                if !editor::confirm("Edit the exercise again?", false)? {
+                    return Err(user_error(format!(
+                        "Exercise {} of {} was not changed",
+                        exercise.exercise_name, skill
+                    )));
+                }
+            }
+        }
+    };
+
+    if edited == original {
+        out.done(format!(
+            "Exercise {} of {} was not changed",
+            exercise.exercise_name, skill
+        ))?;
+        return Ok(false);
+    }
+
+    // check the new name before changing anything, so a taken name doesn't
+    // leave the contents half saved
+    if edited.exercise_name != original.exercise_name {
+        scheduler.rename_exercise(skill, &original.exercise_name, &edited.exercise_name)?;
+    }
+    scheduler.update_exercise(skill, &edited.exercise_name, &edited.markdown)?;
+    out.done(format!(
+        "Updated exercise {} of {}",
+        edited.exercise_name, skill
+    ))?;
+
+    Ok(true)
+}
+
+pub fn remove_exercise(
+    out: &Printer,
+    scheduler: &mut SchedulePlanner,
+    skill: &str,
+    exercise: &str,
+) -> Result<bool> {
+    scheduler.archive_exercise(skill, exercise)?;
+    out.done(format!(
+        "Moved exercise {} of {} to the trash",
+        exercise, skill
+    ))?;
+
+    Ok(true)
+}
+
+fn config_value(scheduler: &SchedulePlanner, key: ConfigKey) -> ConfigValue {
+    let config = &scheduler.config;
+    match key {
+        ConfigKey::SkillPracticeTime => {
+            ConfigValue::Number(config.skill_practice_time.num_minutes() as usize)
+        }
+        ConfigKey::SkillRepeatDays => ConfigValue::Number(config.skill_repeat_days),
+        ConfigKey::SkillsPerDay => ConfigValue::Number(config.skills_per_day),
+        ConfigKey::Overtime => ConfigValue::Switch(config.overtime),
+        ConfigKey::ExcludePracticedToday => ConfigValue::Switch(config.exclude_practiced_today),
+    }
+}
+
+fn config_name(key: ConfigKey) -> &'static str {
+    key.to_possible_value()
+        .expect("config keys are never skipped")
+        .get_name()
+}
+
+pub fn config_get(
+    out: &Printer,
+    scheduler: &SchedulePlanner,
+    key: Option<ConfigKey>,
+) -> Result<bool> {
+    let keys = match key {
+        Some(key) => vec![key],
+        None => ConfigKey::value_variants().to_vec(),
+    };
+    let config = keys
+        .iter()
+        .map(|key| (config_name(*key), config_value(scheduler, *key)))
+        .collect::<ConfigOutput>();
+
+    out.print(&config, |config| match key {
+        Some(key) => println!("{}", config[config_name(key)]),
+        None => {
+            for (name, value) in config.iter() {
+                println!("{} = {}", name, value);
+            }
+        }
+    })?;
+
+    Ok(false)
+}
+
+fn parse_count(key: ConfigKey, value: &str) -> Result<usize> {
+    let value: usize = value
+        .parse()
+        .map_err(|_| user_error(format!("{} must be a whole number", config_name(key))))?;
+    if value == 0 {
+        return Err(user_error(format!(
+            "{} must be greater than zero",
+            config_name(key)
+        )));
+    }
+
+    Ok(value)
+}
+
+fn parse_switch(key: ConfigKey, value: &str) -> Result<bool> {
+    value
+        .parse()
+        .map_err(|_| user_error(format!("{} must be true or false", config_name(key))))
+}
+
+pub fn config_set(
+    out: &Printer,
+    scheduler: &mut SchedulePlanner,
+    key: ConfigKey,
+    value: &str,
+) -> Result<bool> {
+    let config = &mut scheduler.config;
+    match key {
+        ConfigKey::SkillPracticeTime => {
+            config.skill_practice_time = Duration::minutes(parse_count(key, value)? as i64)
+        }
+        ConfigKey::SkillRepeatDays => config.skill_repeat_days = parse_count(key, value)?,
+        ConfigKey::SkillsPerDay => config.skills_per_day = parse_count(key, value)?,
+        ConfigKey::Overtime => config.overtime = parse_switch(key, value)?,
+        ConfigKey::ExcludePracticedToday => {
+            config.exclude_practiced_today = parse_switch(key, value)?
+        }
+    }
+    let config = ConfigOutput::from([(config_name(key), config_value(scheduler, key))]);
+    out.print(&config, |config| {
+        for (name, value) in config.iter() {
+            println!("{} = {}", name, value);
+        }
+    })?;
+
+    Ok(true)
+}
+
+/// Without an output file the export itself is printed, which is JSON in any
+/// format.
+pub fn export(out: &Printer, scheduler: &SchedulePlanner, output: Option<&Path>) -> Result<bool> {
+    let json = scheduler.export_json(Utc::now())?;
+    match output {
+        Some(path) => {
+            fs::write(path, json)
+                .map_err(|e| user_error(format!("Unable to write {}: {}", path.display(), e)))?;
+            out.done(format!("Exported to {}", path.display()))?;
+        }
+        None => println!("{}", json),
+    }
+
+    Ok(false)
+}
+
+pub fn import(out: &Printer, scheduler: &mut SchedulePlanner, file: Option<&Path>) -> Result<bool> {
+    let json = read_input(file)?;
+    let imported = ImportOutput {
+        added_sessions: scheduler.import_json(&json)?,
+    };
+    out.print(&imported, |imported| {
+        println!(
+            "Imported settings and {} new practice sessions",
+            imported.added_sessions
+        )
+    })?;
+
+    Ok(true)
+}
@@ -0,0 +1,82 @@
+//! Editing text in the user's editor, as git does for commit messages.
+
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use crate::output::user_error;
+
+/// Used when neither `$VISUAL` nor `$EDITOR` is set.
+const DEFAULT_EDITOR: &str = "vi";
+
+/// The editor command to run, which may include arguments such as
+/// `code --wait`.
+fn editor_command() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string())
+}
+
+/// Opens `text` in the user's editor and returns the saved contents.
+/// `extension` lets the editor pick its syntax highlighting.
+pub fn edit(text: &str, extension: &str) -> Result<String> {
+    // created with a random name that nothing else can have claimed, and
+    // removed when dropped
+    let mut file = tempfile::Builder::new()
+        .prefix("ppcli-")
+        .suffix(&format!(".{}", extension))
+        .tempfile()
+        .context("Unable to create a temporary file to edit")?;
+    let path = file.path().to_path_buf();
+    file.write_all(text.as_bytes())
+        .and_then(|_| file.flush())
+        .with_context(|| format!("Unable to write {}", path.display()))?;
+
+    let editor = editor_command();
+    let mut args = editor.split_whitespace();
+    let program = args.next().unwrap_or(DEFAULT_EDITOR);
+    let status = Command::new(program).args(args).arg(&path).status();
+    let edited = fs::read_to_string(&path);
+    drop(file);
+
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => {
+            return Err(user_error(format!(
+                "The editor {:?} exited with {}",
+                editor, status
+            )))
+        }
+        Err(e) => {
+            return Err(user_error(format!(
+                "Unable to run the editor {:?}, set $EDITOR: {}",
+                editor, e
+            )))
+        }
+    }
+
+    edited.with_context(|| format!("Unable to read {}", path.display()))
+}
+
+/// Asks a yes or no question on stderr. An empty answer, or none at all
+/// once stdin is closed, gives `default`.
+pub fn confirm(question: &str, default: bool) -> Result<bool> {
+    let choices = if default { "[Y/n]" } else { "[y/N]" };
+    eprint!("{} {} ", question, choices);
+    io::stderr().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    let answer = answer.trim().to_lowercase();
+
+    match answer.as_str() {
+        "" => Ok(default),
+        "y" | "yes" => Ok(true),
+        _ => Ok(false),
+    }
+}
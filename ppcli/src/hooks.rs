@@ -0,0 +1,86 @@
+//! Runs the shell commands configured in `config.toml` on practice session
+//! events.
+//!
+//! Each command gets the event as JSON on stdin, and its details in the
+//! `PPCLI_EVENT`, `PPCLI_TIME`, `PPCLI_SKILL` and `PPCLI_SKILL_INDEX`
+//! environment variables where they apply. Commands run in the background
+//! with their output discarded, so they can't hold up or draw over the
+//! practice screen.
+
+use std::io::Write;
+use std::process::{Child, Command, Stdio};
+
+use anyhow::{Context, Result};
+use pplib::{SessionEvent, SessionListener};
+
+use crate::settings::Hooks;
+
+#[derive(Debug)]
+pub struct CommandHook {
+    hooks: Hooks,
+    /// Commands still running, waited on as new events come in so they don't
+    /// linger as zombies.
+    running: Vec<Child>,
+}
+
+impl CommandHook {
+    pub fn new(hooks: Hooks) -> Self {
+        CommandHook {
+            hooks,
+            running: vec![],
+        }
+    }
+}
+
+#[cfg(unix)]
+fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
+
+impl SessionListener for CommandHook {
+    fn on_event(&mut self, event: &SessionEvent) -> Result<()> {
+        self.running
+            .retain_mut(|child| matches!(child.try_wait(), Ok(None)));
+
+        let command = match self.hooks.command(event.name()) {
+            Some(command) => command,
+            None => return Ok(()),
+        };
+
+        let mut shell = shell_command(command);
+        shell
+            .env("PPCLI_EVENT", event.name())
+            .env("PPCLI_TIME", event.time().to_rfc3339())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        if let Some(skill_name) = event.skill_name() {
+            shell.env("PPCLI_SKILL", skill_name);
+        }
+        if let SessionEvent::SkillStarted { skill_index, .. }
+        | SessionEvent::SkillEnded { skill_index, .. } = event
+        {
+            shell.env("PPCLI_SKILL_INDEX", skill_index.to_string());
+        }
+
+        let mut child = shell
+            .spawn()
+            .with_context(|| format!("Unable to run the {} hook {:?}", event.name(), command))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // the command may not read its stdin, which isn't an error
+            let _ = writeln!(stdin, "{}", serde_json::to_string(event)?);
+        }
+        self.running.push(child);
+
+        Ok(())
+    }
+}
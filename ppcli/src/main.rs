@@ -1,330 +1,393 @@
-#[macro_use]
-extern crate lazy_static;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand, ValueEnum};
+use pplib::{profile_save_path, PracticeProfiles, SchedulePlanner, SegmentKind, DEFAULT_PROFILE};
+
+mod commands;
+mod editor;
+mod hooks;
+mod output;
+mod render;
+mod session_file;
+mod settings;
+mod tui;
+
+use crate::output::{user_error, OutputFormat, Printer, StorageError};
+use crate::settings::Settings;
+
+/// Plan and run daily music practice sessions.
+#[derive(Parser)]
+#[clap(name = "ppcli", version)]
+struct Cli {
+    /// The practice profile to use
+    #[clap(long, global = true, default_value = DEFAULT_PROFILE)]
+    profile: String,
+
+    /// Where to save practice data, instead of the per-user data directory
+    #[clap(long, global = true, env = "PPCLI_DATA_DIR")]
+    data_dir: Option<PathBuf>,
+
+    /// Where to read config.toml from, instead of the per-user config directory
+    #[clap(long, global = true, env = "PPCLI_CONFIG_DIR")]
+    config_dir: Option<PathBuf>,
+
+    /// How to print results and errors; csv is only available for report
+    #[clap(long, global = true, value_enum, default_value = "text")]
+    format: OutputFormat,
+
+    #[clap(subcommand)]
+    command: Command,
+}
 
-use std;
-use std::collections::BTreeMap;
-use std::collections::HashSet;
-use std::fs::File;
-use std::io::prelude::*;
-use std::path::Path;
-use std::thread;
+#[derive(Subcommand)]
+enum Command {
+    /// Show today's practice schedule, or change it before practicing
+    Today {
+        #[clap(subcommand)]
+        edit: Option<TodayCommand>,
+    },
+    /// Pick a new practice schedule for today, keeping the pinned skills
+    Shuffle,
+    /// Practice today's schedule on an interactive timer
+    Practice,
+    /// Show past practice sessions and their notes
+    History {
+        /// How many days of history to show
+        #[clap(long, default_value_t = 7)]
+        days: usize,
+    },
+    /// Show the number of consecutive days practiced
+    Streak,
+    /// Summarize practice over the last week, month or a range of days
+    Report {
+        /// The last 7 days, the default
+        #[clap(long, group = "period")]
+        week: bool,
+        /// The last 30 days
+        #[clap(long, group = "period")]
+        month: bool,
+        /// The days from START to END inclusive, as YYYY-MM-DD
+        #[clap(long, group = "period", number_of_values = 2, value_names = &["START", "END"])]
+        range: Option<Vec<NaiveDate>>,
+    },
+    /// Record practice done away from the app, such as at a lesson
+    Log {
+        /// Skills practiced, as NAME or NAME=MINUTES. Skills without minutes
+        /// count as practiced for the configured skill practice time.
+        #[clap(required = true)]
+        skills: Vec<String>,
+        /// The day practiced as YYYY-MM-DD, today if omitted
+        #[clap(long)]
+        date: Option<NaiveDate>,
+    },
+    /// Manage the skills to practice
+    #[clap(subcommand)]
+    Skills(SkillsCommand),
+    /// Manage the exercises of a skill
+    #[clap(subcommand)]
+    Exercises(ExercisesCommand),
+    /// Set the skill practiced at the start of every session
+    #[clap(subcommand)]
+    WarmUp(SegmentCommand),
+    /// Set the skill practiced at the end of every session
+    #[clap(subcommand)]
+    CoolDown(SegmentCommand),
+    /// Rest between skills, such as 5 minutes after every 3 skills
+    #[clap(subcommand)]
+    RestBreaks(RestBreaksCommand),
+    /// Cycle through the skills in several short rounds instead of one
+    /// long block each
+    #[clap(subcommand)]
+    Interleave(InterleaveCommand),
+    /// Show or change the practice settings
+    #[clap(subcommand)]
+    Config(ConfigCommand),
+    /// Write the settings and history to a file the webapp can import
+    Export {
+        /// File to write, written to stdout if omitted
+        #[clap(long)]
+        output: Option<PathBuf>,
+    },
+    /// Replace the settings with an exported file's, merging in its history
+    Import {
+        /// Exported file to read, read from stdin if omitted
+        file: Option<PathBuf>,
+    },
+}
 
-use anyhow::Result;
-use chrono::{Date, DateTime, Duration, Utc};
-use rand::prelude::*;
-use rand::seq::SliceRandom;
-use serde::{Deserialize, Serialize};
-use text_io::read;
-use thiserror::Error;
+/// Changes to today's schedule last until it's practiced or the day ends.
+#[derive(Subcommand)]
+enum TodayCommand {
+    /// Keep a skill in the schedule when it's shuffled
+    Pin { skill: String },
+    /// Let a pinned skill be shuffled out again
+    Unpin { skill: String },
+    /// Replace a skill with another pick
+    Swap { skill: String },
+    /// Leave a skill out for the rest of the day, replacing it if possible
+    Exclude { skill: String },
+    /// Add a skill to the end of the schedule, pinned
+    Add { skill: String },
+}
 
-lazy_static! {
-    pub(crate) static ref DEFAULT_CATEGORIES: Vec<PracticeSkill> = vec![
-        PracticeSkill {
-            skill_name: "Ear Training".to_string(),
-        },
-        PracticeSkill {
-            skill_name: "Exercises".to_string(),
-        },
-        PracticeSkill {
-            skill_name: "Chords".to_string(),
-        },
-        PracticeSkill {
-            skill_name: "Scales".to_string(),
-        },
-        PracticeSkill {
-            skill_name: "Sight Reading".to_string(),
-        },
-        PracticeSkill {
-            skill_name: "Music Theory".to_string(),
-        },
-        PracticeSkill {
-            skill_name: "Improvisation".to_string(),
-        },
-        PracticeSkill {
-            skill_name: "Songwriting".to_string(),
-        },
-    ];
+#[derive(Subcommand)]
+enum SkillsCommand {
+    /// List the skills and their exercises
+    List,
+    /// Add a skill
+    Add { name: String },
+    /// Rename a skill, keeping its history
+    Rename { name: String, new_name: String },
+    /// Move a skill to the trash
+    Remove { name: String },
+    /// Restore a skill from the trash
+    Restore { name: String },
 }
 
-#[derive(Error, Debug)]
-pub enum SchedulerError {
-    // #[error("Invalid header (expected {expected:?}, got {found:?})")]
-    // InvalidHeader {
-    //     expected: String,
-    //     found: String,
-    // },
-    #[error("You must have at least 4 skills to practice")]
-    MissingSkills(),
+#[derive(Subcommand)]
+enum ExercisesCommand {
+    /// Add an exercise to a skill
+    Add {
+        skill: String,
+        name: String,
+        /// Markdown file with the exercise contents, read from stdin if omitted
+        #[clap(long)]
+        file: Option<PathBuf>,
+    },
+    /// Show the contents of an exercise, with its tablature as ASCII tab
+    Show { skill: String, exercise: String },
+    /// Edit an exercise in $EDITOR, or replace its contents from a file
+    Edit {
+        skill: String,
+        exercise: String,
+        /// Markdown file with the exercise contents. Read from stdin if omitted
+        /// and stdin isn't a terminal, otherwise the exercise opens in $EDITOR.
+        #[clap(long)]
+        file: Option<PathBuf>,
+    },
+    /// Move an exercise to the trash
+    Remove { skill: String, exercise: String },
+}
 
-    #[error(transparent)]
-    Other(#[from] anyhow::Error), // source and Display delegate to anyhow::Error
+/// Warm-up and cool-down skills aren't scheduled on their own.
+#[derive(Subcommand)]
+enum SegmentCommand {
+    /// Practice a skill's exercises for some minutes
+    Set {
+        skill: String,
+        #[clap(long)]
+        minutes: usize,
+    },
+    /// Stop practicing it
+    Clear,
 }
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
-pub struct PracticeActivity {
-    pub activity_name: String,
+#[derive(Subcommand)]
+enum RestBreaksCommand {
+    /// Rest for some minutes after every few skills
+    Set {
+        /// How many skills to practice between breaks
+        #[clap(long, default_value_t = 1)]
+        every: usize,
+        #[clap(long)]
+        minutes: usize,
+    },
+    /// Stop taking rest breaks
+    Clear,
 }
 
-#[derive(Serialize, Deserialize, Clone, Hash, PartialOrd, Ord, PartialEq, Eq, Debug)]
-pub struct PracticeSkill {
-    pub skill_name: String,
+#[derive(Subcommand)]
+enum InterleaveCommand {
+    /// Practice each skill for some minutes in every round
+    Set {
+        #[clap(long)]
+        rounds: usize,
+        #[clap(long)]
+        minutes: usize,
+    },
+    /// Practice each skill in one block
+    Clear,
 }
 
-#[serde_with::serde_as]
-#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
-pub struct SchedulePlanner {
-    /// The duration each skill is to be practiced.
-    #[serde_as(as = "serde_with::DurationSeconds<i64>")]
-    skill_practice_time: Duration,
-    /// The max number of days allowed to elapse without practicing a skill.
-    skill_repeat_days: usize,
-    /// The number of skills to practice per day.
-    skills_per_day: usize,
-    skills: Vec<PracticeSkill>,
-    /// BTreeMap containing historical practice sessions.
-    history: BTreeMap<DateTime<Utc>, Vec<PracticeSkill>>,
-    todays_schedule: Option<Vec<PracticeSkill>>,
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Show a setting, or all settings if none is given
+    Get {
+        #[clap(value_enum)]
+        key: Option<ConfigKey>,
+    },
+    /// Change a setting
+    Set {
+        #[clap(value_enum)]
+        key: ConfigKey,
+        value: String,
+    },
 }
 
-impl SchedulePlanner {
-    pub fn new() -> Self {
-        // Call this here so it's cached and faster later
-        // let (_stream, _stream_handle) = OutputStream::try_default().unwrap();
-        SchedulePlanner {
-            skill_practice_time: Duration::minutes(15),
-            skill_repeat_days: 2,
-            skills: DEFAULT_CATEGORIES.to_vec(),
-            history: BTreeMap::new(),
-            todays_schedule: None,
-            skills_per_day: 4,
-        }
-    }
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ConfigKey {
+    /// Minutes to practice each skill
+    SkillPracticeTime,
+    /// The max number of days allowed to elapse without practicing a skill
+    SkillRepeatDays,
+    /// The number of skills to practice per day
+    SkillsPerDay,
+    /// Whether a skill's timer keeps counting past zero until you move on,
+    /// true or false
+    Overtime,
+    /// Whether a later session on the same day leaves out the skills already
+    /// practiced that day, true or false
+    ExcludePracticedToday,
+}
 
-    pub fn get_todays_schedule(&self) -> Option<&Vec<PracticeSkill>> {
-        self.todays_schedule.as_ref()
+fn load_scheduler(save_path: &Path) -> Result<SchedulePlanner> {
+    if !save_path.exists() {
+        return Ok(SchedulePlanner::new());
     }
 
-    /// Returns the skills seen in the last n days of history as a HashSet<&PracticeSkill>
-    pub fn get_history_n_days_back(
-        &self,
-        n: usize,
-    ) -> Result<BTreeMap<Date<Utc>, HashSet<&PracticeSkill>>> {
-        let now = Utc::now();
-        let n_days_back = now.checked_sub_signed(Duration::days(n.try_into().unwrap()));
-        if n_days_back.is_none() {
-            return Err(anyhow::anyhow!("Invalid historical search term"));
-        }
-        let mut historical_skills: BTreeMap<Date<Utc>, HashSet<&PracticeSkill>> = BTreeMap::new();
-
-        // TODO keys are sorted so we could shorten iteration here
-        for (key, value) in self.history.iter() {
-            println!("key: {}", key);
-            // if the history item is within the last n days...
-            if key > &n_days_back.unwrap() {
-                for v in value {
-                    // insert into the HashSet for that day
-                    let day_skills = match historical_skills.contains_key(&key.date()) {
-                        true => historical_skills.get_mut(&key.date()).unwrap(),
-                        false => {
-                            let hs = HashSet::new();
-                            historical_skills.insert(key.date(), hs);
-                            historical_skills.get_mut(&key.date()).unwrap()
-                        }
-                    };
+    SchedulePlanner::new_from_disk(save_path)
+        .with_context(|| StorageError(format!("Unable to load {}", save_path.display())))
+}
 
-                    day_skills.insert(v);
-                }
-            }
+fn segment(
+    out: &Printer,
+    scheduler: &mut SchedulePlanner,
+    kind: SegmentKind,
+    cmd: SegmentCommand,
+) -> Result<bool> {
+    match cmd {
+        SegmentCommand::Set { skill, minutes } => {
+            commands::set_segment(out, scheduler, kind, &skill, minutes)
         }
-
-        Ok(historical_skills)
+        SegmentCommand::Clear => commands::clear_segment(out, scheduler, kind),
     }
+}
 
-    /// Iterate every history item to determine the total days of history
-    /// that have been seen.
-    /// TODO requires iteration and allocation to calculate, could just be a counter
-    pub fn days_of_history(&self) -> usize {
-        let mut seen_days = HashSet::new();
-        for (key, _value) in self.history.iter() {
-            seen_days.insert(key.date());
-        }
-
-        seen_days.len()
+fn run(cli: Cli) -> Result<()> {
+    PracticeProfiles::validate_name(&cli.profile)?;
+    if cli.format == OutputFormat::Csv && !matches!(cli.command, Command::Report { .. }) {
+        return Err(user_error("CSV output is only available for report"));
     }
+    let out = Printer { format: cli.format };
 
-    pub fn update_todays_schedule(&mut self, force_update: bool) -> Result<(), SchedulerError> {
-        if self.todays_schedule.is_some() && !force_update {
-            // schedule is already set and we didn't force an update
-            return Ok(());
-        }
-
-        if self.skills.len() == 0 {
-            return Err(SchedulerError::MissingSkills());
-        }
-
-        let past_history = self.get_history_n_days_back(self.skill_repeat_days)?;
-        let prob_bandwidth: f64 = 100.0 / self.skill_repeat_days as f64;
-
-        let mut probabilities: BTreeMap<&PracticeSkill, u64> = BTreeMap::new();
-
-        for skill in &self.skills {
-            let mut seen = false;
-            let mut d = 0;
-            for (_day, day_skills) in past_history.iter() {
-                println!("On day: {}", _day);
-                if day_skills.contains(skill) {
-                    seen = true;
-                }
-
-                // if we have seen this before, weight the probability by the day seen
-                if seen {
-                    probabilities.insert(skill, prob_bandwidth as u64 * d);
-                }
-                d = d + 1;
+    let config_dir = match cli.config_dir {
+        Some(dir) => dir,
+        None => settings::default_config_dir()?,
+    };
+    let settings = Settings::load(&config_dir).context(StorageError(format!(
+        "Unable to read the settings in {}",
+        config_dir.display()
+    )))?;
+    let data_dir = match cli.data_dir.or(settings.data_dir) {
+        Some(dir) => dir,
+        None => settings::default_data_dir()?,
+    };
+    settings::create_data_dir(&data_dir).context(StorageError(format!(
+        "Unable to use the data directory {}",
+        data_dir.display()
+    )))?;
+    let save_path = profile_save_path(&data_dir, &cli.profile);
+    let mut scheduler = load_scheduler(&save_path)?;
+
+    let modified = match cli.command {
+        Command::Today { edit } => match edit {
+            None => commands::today(&out, &mut scheduler)?,
+            Some(TodayCommand::Pin { skill }) => {
+                commands::edit_today(&out, &mut scheduler, |s, _| s.pin_skill(&skill, true))?
             }
-
-            // if any skills do not appear in last n days history at all, set probability to 100%
-            if !seen {
-                probabilities.insert(skill, 100);
-                continue;
+            Some(TodayCommand::Unpin { skill }) => {
+                commands::edit_today(&out, &mut scheduler, |s, _| s.pin_skill(&skill, false))?
             }
-        }
-
-        self.todays_schedule = Some(
-            probabilities
-                .iter()
-                .collect::<Vec<_>>()
-                .choose_multiple_weighted(&mut thread_rng(), self.skills_per_day, |item| {
-                    item.1.to_owned() as f64
-                })
-                .unwrap()
-                .map(|item| item.0.to_owned().to_owned())
-                .collect::<Vec<PracticeSkill>>(),
-        );
-        return Ok(());
-    }
-
-    pub fn start_skill(&self, skill: &PracticeSkill) -> Result<()> {
-        println!(
-            "Starting {} minute practice for skill: {:#?}",
-            self.skill_practice_time.num_minutes(),
-            skill
-        );
-
-        thread::sleep(self.skill_practice_time.to_std()?);
-        println!("Done practicing skill: {:#?}", skill);
-        // self.play_ding_sound();
-
-        Ok(())
-    }
-
-    pub fn play_ding_sound(&self) -> Result<()> {
-        // TODO these should be singletons or maybe the Sink type idk or a separate audio player task
-        // Get a output stream handle to the default physical sound device
-        // let (_stream, stream_handle) = OutputStream::try_default().unwrap();
-        // // Load a sound from a file, using a path relative to Cargo.toml
-        // let file = BufReader::new(File::open("audio/ding.wav").unwrap());
-        // // Decode that sound file into a source
-        // let source = Decoder::new(file).unwrap();
-
-        // stream_handle.play_raw(source.convert_samples())?;
-
-        // need to sleep for the duration of the ding sound
-        // (todo should probably move to a second thread/task/whatever in tokio)
-        thread::sleep(std::time::Duration::from_secs(2));
-        Ok(())
-    }
-
-    pub fn start_daily_practice(&mut self) -> Result<()> {
-        // ensure today's schedule has been set
-        self.update_todays_schedule(false)?;
-        for skill in self.todays_schedule.as_ref().unwrap().iter() {
-            println!("Starting practice for skill: {:#?}", skill);
-            self.start_skill(&skill)?;
-        }
-        println!("Finished practicing for today!");
-
-        // record this practice session in history, save to disk
-        self.mark_todays_practice_completed()?;
-
-        Ok(())
-    }
-
-    fn mark_todays_practice_completed(&mut self) -> Result<()> {
-        if self.todays_schedule.is_none() {
-            return Err(anyhow::anyhow!(
-                "today's practice must be set to mark completed"
-            ));
-        }
-
-        // append today's practice to the history
-        let now = Utc::now();
-        self.history
-            .insert(now, self.todays_schedule.as_ref().unwrap().to_vec());
-
-        // unset today's practice on Self
-        self.todays_schedule = None;
-
-        self.save_to_disk()?;
-        Ok(())
-    }
-
-    pub fn save_to_disk(&self) -> Result<()> {
-        let encoded: Vec<u8> = bincode::serialize(self).unwrap();
-        let mut file = File::create("./saved_data/history.bin")?;
-        file.write_all(&encoded)?;
+            Some(TodayCommand::Swap { skill }) => {
+                commands::edit_today(&out, &mut scheduler, |s, now| {
+                    s.swap_skill(&skill, now).map(|_| ())
+                })?
+            }
+            Some(TodayCommand::Exclude { skill }) => {
+                commands::edit_today(&out, &mut scheduler, |s, now| {
+                    s.exclude_skill(&skill, now).map(|_| ())
+                })?
+            }
+            Some(TodayCommand::Add { skill }) => {
+                commands::edit_today(&out, &mut scheduler, |s, _| s.add_skill_today(&skill))?
+            }
+        },
+        Command::Shuffle => commands::shuffle(&out, &mut scheduler)?,
+        Command::Practice => commands::practice(&out, &mut scheduler, &settings.hooks, &save_path)?,
+        Command::History { days } => commands::history(&out, &scheduler, days)?,
+        Command::Streak => commands::streak(&out, &scheduler)?,
+        Command::Report {
+            week: _,
+            month,
+            range,
+        } => commands::report(&out, &scheduler, month, range.as_deref())?,
+        Command::Log { skills, date } => commands::log(&out, &mut scheduler, &skills, date)?,
+        Command::Skills(cmd) => match cmd {
+            SkillsCommand::List => commands::list_skills(&out, &scheduler)?,
+            SkillsCommand::Add { name } => commands::add_skill(&out, &mut scheduler, &name)?,
+            SkillsCommand::Rename { name, new_name } => {
+                commands::rename_skill(&out, &mut scheduler, &name, &new_name)?
+            }
+            SkillsCommand::Remove { name } => commands::remove_skill(&out, &mut scheduler, &name)?,
+            SkillsCommand::Restore { name } => {
+                commands::restore_skill(&out, &mut scheduler, &name)?
+            }
+        },
+        Command::Exercises(cmd) => match cmd {
+            ExercisesCommand::Add { skill, name, file } => {
+                commands::add_exercise(&out, &mut scheduler, &skill, &name, file.as_deref())?
+            }
+            ExercisesCommand::Show { skill, exercise } => {
+                commands::show_exercise(&out, &scheduler, &skill, &exercise)?
+            }
+            ExercisesCommand::Edit {
+                skill,
+                exercise,
+                file,
+            } => commands::edit_exercise(&out, &mut scheduler, &skill, &exercise, file.as_deref())?,
+            ExercisesCommand::Remove { skill, exercise } => {
+                commands::remove_exercise(&out, &mut scheduler, &skill, &exercise)?
+            }
+        },
+        Command::WarmUp(cmd) => segment(&out, &mut scheduler, SegmentKind::WarmUp, cmd)?,
+        Command::CoolDown(cmd) => segment(&out, &mut scheduler, SegmentKind::CoolDown, cmd)?,
+        Command::RestBreaks(cmd) => match cmd {
+            RestBreaksCommand::Set { every, minutes } => {
+                commands::set_rest_breaks(&out, &mut scheduler, every, minutes)?
+            }
+            RestBreaksCommand::Clear => commands::clear_rest_breaks(&out, &mut scheduler)?,
+        },
+        Command::Interleave(cmd) => match cmd {
+            InterleaveCommand::Set { rounds, minutes } => {
+                commands::set_interleaving(&out, &mut scheduler, rounds, minutes)?
+            }
+            InterleaveCommand::Clear => commands::clear_interleaving(&out, &mut scheduler)?,
+        },
+        Command::Config(cmd) => match cmd {
+            ConfigCommand::Get { key } => commands::config_get(&out, &scheduler, key)?,
+            ConfigCommand::Set { key, value } => {
+                commands::config_set(&out, &mut scheduler, key, &value)?
+            }
+        },
+        Command::Export { output } => commands::export(&out, &scheduler, output.as_deref())?,
+        Command::Import { file } => commands::import(&out, &mut scheduler, file.as_deref())?,
+    };
 
-        Ok(())
+    if modified {
+        scheduler
+            .save_to_disk(&save_path)
+            .with_context(|| StorageError(format!("Unable to save {}", save_path.display())))?;
     }
 
-    pub fn new_from_disk() -> Result<Self> {
-        let mut f = File::open("./saved_data/history.bin")?;
-        let mut buffer = Vec::new();
-        f.read_to_end(&mut buffer)?;
-        let decoded: Self = bincode::deserialize(&buffer[..])?;
-
-        Ok(decoded)
-    }
+    Ok(())
 }
 
 fn main() {
-    let mut scheduler = match Path::new("./saved_data/history.bin").exists() {
-        true => {
-            println!("Saved data found, loading...");
-            match SchedulePlanner::new_from_disk() {
-                Ok(sp) => sp,
-                Err(_e) => {
-                    // TODO the import/export mechanism is extremely fragile
-                    // if the data structure is changed
-                    println!("Error loading history file");
-                    SchedulePlanner::new()
-                }
-            }
-        }
-        false => SchedulePlanner::new(),
-    };
-
-    scheduler
-        .update_todays_schedule(false)
-        .expect("Unable to update today's schedule");
-    let todays_schedule = scheduler.get_todays_schedule();
-    println!("Today's schedule: {:#?}", todays_schedule);
-    println!("Want to practice? ");
-    let line: String = read!("{}\n");
-    match line.to_lowercase().as_str() {
-        "y\r" | "y\n" | "y" => {
-            println!("Yeehaw");
-            scheduler
-                .start_daily_practice()
-                .expect("Unable to run daily practice");
-        }
-        _ => {
-            println!("Well, okay then.");
-            println!("{}", line);
-        }
-    };
-}
-
-pub fn get_todays_schedule() -> Result<Vec<PracticeSkill>> {
-    Ok(vec![])
+    let cli = Cli::parse();
+    let format = cli.format;
+    if let Err(err) = run(cli) {
+        std::process::exit(output::print_error(format, &err));
+    }
 }
@@ -0,0 +1,244 @@
+//! Printing command results for people or for scripts.
+//!
+//! With `--format json` every command prints exactly one JSON document to
+//! stdout, including when it fails. The types here define that schema, so
+//! fields should only ever be added to them.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use pplib::SchedulerError;
+use serde::Serialize;
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    /// Only supported by the report command
+    Csv,
+}
+
+/// Prints command results in the chosen format.
+pub struct Printer {
+    pub format: OutputFormat,
+}
+
+impl Printer {
+    /// Prints `value` as JSON, or with `text` for people.
+    pub fn print<T: Serialize>(&self, value: &T, text: impl FnOnce(&T)) -> Result<()> {
+        match self.format {
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(value)?),
+            OutputFormat::Text | OutputFormat::Csv => text(value),
+        }
+
+        Ok(())
+    }
+
+    /// Reports that a command changing the planner succeeded.
+    pub fn done(&self, message: String) -> Result<()> {
+        self.print(&Done { message }, |done| println!("{}", done.message))
+    }
+}
+
+#[derive(Serialize)]
+pub struct Done {
+    pub message: String,
+}
+
+#[derive(Serialize)]
+pub struct ScheduleOutput {
+    /// Practice sessions already completed today, so the schedule is for
+    /// the session after them.
+    pub sessions_completed_today: usize,
+    pub skill_practice_minutes: i64,
+    pub skills: Vec<String>,
+    /// The skills kept when the schedule is shuffled.
+    pub pinned: Vec<String>,
+    pub warm_up: Option<SegmentOutput>,
+    pub cool_down: Option<SegmentOutput>,
+    pub rest_breaks: Option<RestBreaksOutput>,
+    pub interleaving: Option<InterleavingOutput>,
+}
+
+#[derive(Serialize)]
+pub struct SegmentOutput {
+    pub skill_name: String,
+    pub minutes: i64,
+}
+
+#[derive(Serialize)]
+pub struct RestBreaksOutput {
+    pub every_skills: usize,
+    pub minutes: i64,
+}
+
+#[derive(Serialize)]
+pub struct InterleavingOutput {
+    pub rounds: usize,
+    /// How long each skill is practiced in each round.
+    pub minutes: i64,
+}
+
+#[derive(Serialize)]
+pub struct PracticeOutput {
+    /// "completed" or "stopped"
+    pub outcome: &'static str,
+}
+
+#[derive(Serialize)]
+pub struct HistorySkill {
+    pub skill_name: String,
+    /// Missing for sessions recorded before durations were kept.
+    pub minutes: Option<i64>,
+    pub note: Option<String>,
+    /// Whether the skill was skipped before its time ran out.
+    pub skipped: bool,
+}
+
+#[derive(Serialize)]
+pub struct HistoryEntry {
+    pub time: DateTime<Utc>,
+    pub skills: Vec<HistorySkill>,
+}
+
+#[derive(Serialize)]
+pub struct StreakOutput {
+    pub streak: usize,
+}
+
+#[derive(Serialize)]
+pub struct LogOutput {
+    pub time: DateTime<Utc>,
+    pub streak: usize,
+}
+
+#[derive(Serialize)]
+pub struct SkillOutput {
+    pub skill_name: String,
+    pub exercises: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct SkillsOutput {
+    pub skills: Vec<SkillOutput>,
+    pub archived_skills: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct ExerciseOutput {
+    pub skill_name: String,
+    pub exercise_name: String,
+    pub markdown: String,
+}
+
+/// Settings keyed by their command line names.
+pub type ConfigOutput = BTreeMap<&'static str, ConfigValue>;
+
+/// A setting's value, a number or an on/off switch.
+#[derive(Serialize, Clone, Copy)]
+#[serde(untagged)]
+pub enum ConfigValue {
+    Number(usize),
+    Switch(bool),
+}
+
+impl fmt::Display for ConfigValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigValue::Number(value) => write!(f, "{}", value),
+            ConfigValue::Switch(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ImportOutput {
+    pub added_sessions: usize,
+}
+
+/// A mistake in how ppcli was used, such as an invalid argument.
+#[derive(Debug)]
+pub struct UserError(pub String);
+
+impl fmt::Display for UserError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for UserError {}
+
+pub fn user_error(message: impl Into<String>) -> anyhow::Error {
+    UserError(message.into()).into()
+}
+
+/// Context for failures reading or writing ppcli's files.
+#[derive(Debug)]
+pub struct StorageError(pub String);
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Exit codes, so scripts can tell mistakes in their input from failures
+/// they can't fix. Usage errors found by clap also exit with 2.
+pub const EXIT_INTERNAL_ERROR: i32 = 1;
+pub const EXIT_USER_ERROR: i32 = 2;
+pub const EXIT_STORAGE_ERROR: i32 = 3;
+
+#[derive(Serialize)]
+pub struct ErrorDetails {
+    /// A `SchedulerError` variant name, "InvalidInput", "Storage" or
+    /// "Internal"
+    pub kind: &'static str,
+    pub message: String,
+    pub exit_code: i32,
+}
+
+#[derive(Serialize)]
+pub struct ErrorOutput {
+    pub error: ErrorDetails,
+}
+
+/// Sorts an error into its kind and exit code.
+pub fn classify_error(err: &anyhow::Error) -> (&'static str, i32) {
+    if err.downcast_ref::<StorageError>().is_some() {
+        return ("Storage", EXIT_STORAGE_ERROR);
+    }
+    if err.downcast_ref::<UserError>().is_some() {
+        return ("InvalidInput", EXIT_USER_ERROR);
+    }
+
+    match err.downcast_ref::<SchedulerError>() {
+        Some(SchedulerError::Other(_)) | None => ("Internal", EXIT_INTERNAL_ERROR),
+        Some(e) => (e.kind(), EXIT_USER_ERROR),
+    }
+}
+
+/// Prints a failed command's error and returns the exit code to use.
+pub fn print_error(format: OutputFormat, err: &anyhow::Error) -> i32 {
+    let (kind, exit_code) = classify_error(err);
+    match format {
+        OutputFormat::Json => {
+            let output = ErrorOutput {
+                error: ErrorDetails {
+                    kind,
+                    message: format!("{:#}", err),
+                    exit_code,
+                },
+            };
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&output).expect("errors serialize")
+            );
+        }
+        OutputFormat::Text | OutputFormat::Csv => eprintln!("Error: {:#}", err),
+    }
+
+    exit_code
+}
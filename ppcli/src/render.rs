@@ -0,0 +1,254 @@
+//! Renders exercise markdown for the terminal, drawing vextab blocks as ASCII
+//! tablature.
+
+use crossterm::style::Attribute;
+use pplib::{split_vextab_blocks, vextab_to_ascii, ExerciseBlock};
+use pulldown_cmark::{Event, HeadingLevel, Parser, Tag};
+
+/// Renders an exercise's markdown contents as lines of at most `width`
+/// columns. Styled output uses terminal attributes for headings, emphasis and
+/// links.
+pub fn render_exercise(markdown: &str, width: usize, styled: bool) -> Vec<String> {
+    let mut lines = vec![];
+    for block in split_vextab_blocks(markdown) {
+        match block {
+            ExerciseBlock::Markdown(markdown) => {
+                lines.extend(render_markdown(&markdown, width, styled))
+            }
+            ExerciseBlock::Vextab(source) => {
+                match vextab_to_ascii(&source) {
+                    Ok(tab) => lines.extend(tab.lines().map(|l| l.to_string())),
+                    Err(err) => lines.push(format!("(unable to show tablature: {})", err)),
+                }
+                lines.push(String::new());
+            }
+        }
+    }
+
+    while lines.last().map(|l| l.is_empty()).unwrap_or(false) {
+        lines.pop();
+    }
+
+    lines
+}
+
+fn render_markdown(markdown: &str, width: usize, styled: bool) -> Vec<String> {
+    let mut renderer = MarkdownRenderer::new(width, styled);
+    for event in Parser::new(markdown) {
+        renderer.event(event);
+    }
+    renderer.finish_line();
+
+    renderer.lines
+}
+
+/// Word-wrapping state while walking the markdown events.
+struct MarkdownRenderer {
+    width: usize,
+    styled: bool,
+    lines: Vec<String>,
+    // the line being built, including any escape codes, and its visible length
+    line: String,
+    line_len: usize,
+    // visible indent of wrapped lines, e.g. under a list bullet
+    indent: usize,
+    pending_space: bool,
+    styles: Vec<Attribute>,
+    // the next number of each open list, or None for bullet lists
+    lists: Vec<Option<u64>>,
+    link: Option<(String, String)>,
+    in_code_block: bool,
+}
+
+impl MarkdownRenderer {
+    fn new(width: usize, styled: bool) -> Self {
+        MarkdownRenderer {
+            width: width.max(20),
+            styled,
+            lines: vec![],
+            line: String::new(),
+            line_len: 0,
+            indent: 0,
+            pending_space: false,
+            styles: vec![],
+            lists: vec![],
+            link: None,
+            in_code_block: false,
+        }
+    }
+
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) if self.in_code_block => {
+                for line in text.lines() {
+                    self.lines.push(format!("    {}", line));
+                }
+            }
+            Event::Text(text) | Event::Code(text) => self.push_text(&text),
+            Event::SoftBreak => self.pending_space = true,
+            Event::HardBreak => self.break_line(),
+            Event::Rule => {
+                self.finish_line();
+                self.lines.push("─".repeat(self.width));
+                self.blank_line();
+            }
+            Event::TaskListMarker(done) => self.push_text(if done { "[x] " } else { "[ ] " }),
+            // other html can't be shown in the terminal
+            Event::Html(_) | Event::FootnoteReference(_) => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Heading(level, _, _) => {
+                self.finish_line();
+                self.styles.push(Attribute::Bold);
+                if level == HeadingLevel::H1 {
+                    self.styles.push(Attribute::Underlined);
+                }
+            }
+            Tag::List(start) => {
+                self.finish_line();
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.finish_line();
+                let depth = self.lists.len().max(1);
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => "• ".to_string(),
+                };
+                self.set_indent((depth - 1) * 2);
+                self.line.push_str(&marker);
+                self.line_len += marker.chars().count();
+                self.indent = self.line_len;
+            }
+            Tag::Emphasis => self.styles.push(Attribute::Italic),
+            Tag::Strong => self.styles.push(Attribute::Bold),
+            Tag::Link(_, url, _) | Tag::Image(_, url, _) => {
+                self.styles.push(Attribute::Underlined);
+                self.link = Some((url.to_string(), String::new()));
+            }
+            Tag::CodeBlock(_) => {
+                self.finish_line();
+                self.in_code_block = true;
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: Tag) {
+        match tag {
+            Tag::Heading(level, _, _) => {
+                self.finish_line();
+                self.styles.pop();
+                if level == HeadingLevel::H1 {
+                    self.styles.pop();
+                }
+                self.blank_line();
+            }
+            Tag::Paragraph => {
+                self.finish_line();
+                if self.lists.is_empty() {
+                    self.blank_line();
+                }
+            }
+            Tag::List(_) => {
+                self.finish_line();
+                self.lists.pop();
+                self.set_indent(self.lists.len() * 2);
+                if self.lists.is_empty() {
+                    self.blank_line();
+                }
+            }
+            Tag::Item => self.finish_line(),
+            Tag::Emphasis | Tag::Strong => {
+                self.styles.pop();
+            }
+            Tag::Link(..) | Tag::Image(..) => {
+                self.styles.pop();
+                if let Some((url, text)) = self.link.take() {
+                    if !url.is_empty() && url != text {
+                        self.push_word(&format!("({})", url), true);
+                    }
+                }
+            }
+            Tag::CodeBlock(_) => {
+                self.in_code_block = false;
+                self.blank_line();
+            }
+            _ => {}
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if let Some((_, link_text)) = self.link.as_mut() {
+            link_text.push_str(text);
+        }
+
+        let mut space = self.pending_space || text.starts_with(char::is_whitespace);
+        for word in text.split_whitespace() {
+            self.push_word(word, space);
+            space = true;
+        }
+        self.pending_space = text.ends_with(char::is_whitespace);
+    }
+
+    fn push_word(&mut self, word: &str, space: bool) {
+        let len = word.chars().count();
+        let mut space = space && self.line_len > self.indent;
+        if space && self.line_len + 1 + len > self.width {
+            self.break_line();
+            space = false;
+        }
+
+        if space {
+            self.line.push(' ');
+            self.line_len += 1;
+        }
+        if self.styled && !self.styles.is_empty() {
+            for style in self.styles.iter() {
+                self.line.push_str(&style.to_string());
+            }
+            self.line.push_str(word);
+            self.line.push_str(&Attribute::Reset.to_string());
+        } else {
+            self.line.push_str(word);
+        }
+        self.line_len += len;
+    }
+
+    /// Starts a new line within the current block.
+    fn break_line(&mut self) {
+        self.lines.push(std::mem::take(&mut self.line));
+        self.line = " ".repeat(self.indent);
+        self.line_len = self.indent;
+    }
+
+    /// Ends the current block's last line, if it has any text.
+    fn finish_line(&mut self) {
+        if self.line_len > self.indent {
+            self.lines.push(std::mem::take(&mut self.line));
+        }
+        self.line = " ".repeat(self.indent);
+        self.line_len = self.indent;
+        self.pending_space = false;
+    }
+
+    fn set_indent(&mut self, indent: usize) {
+        self.indent = indent;
+        self.line = " ".repeat(indent);
+        self.line_len = indent;
+    }
+
+    fn blank_line(&mut self) {
+        if self.lines.last().map(|l| !l.is_empty()).unwrap_or(false) {
+            self.lines.push(String::new());
+        }
+    }
+}
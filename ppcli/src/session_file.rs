@@ -0,0 +1,92 @@
+//! The practice session underway, saved beside a profile's data so it can be
+//! resumed if ppcli is killed or crashes mid-session.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use pplib::{SavedSession, SchedulePlanner};
+
+const SESSION_FILE: &str = "session.json";
+
+/// How often a running session is saved when nothing else happens, which
+/// bounds the practice time lost to a crash.
+const SAVE_INTERVAL_SECONDS: i64 = 5;
+
+pub struct SessionFile {
+    path: PathBuf,
+    last_saved: Option<DateTime<Utc>>,
+}
+
+impl SessionFile {
+    /// The session file of the profile saved at `save_path`.
+    pub fn for_profile(save_path: &Path) -> Self {
+        SessionFile {
+            path: save_path.with_file_name(SESSION_FILE),
+            last_saved: None,
+        }
+    }
+
+    /// The session left behind by a previous run, if any.
+    pub fn load(&self) -> Result<Option<SavedSession>> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+
+        let json = fs::read_to_string(&self.path)
+            .with_context(|| format!("Unable to read {}", self.path.display()))?;
+        let saved = SavedSession::from_json(&json)
+            .with_context(|| format!("Invalid saved session in {}", self.path.display()))?;
+        Ok(Some(saved))
+    }
+
+    /// Saves the session underway, replacing the file in one step so a crash
+    /// while saving doesn't leave half a session behind.
+    pub fn save(&mut self, scheduler: &SchedulePlanner, current_time: DateTime<Utc>) -> Result<()> {
+        let saved = match scheduler.save_session(current_time) {
+            Some(saved) => saved,
+            None => return self.remove(),
+        };
+
+        // a new profile's directory isn't created until its data is saved
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Unable to create {}", parent.display()))?;
+        }
+        let partial = self.path.with_extension("json.partial");
+        fs::write(&partial, saved.to_json()?)
+            .and_then(|_| fs::rename(&partial, &self.path))
+            .with_context(|| format!("Unable to save {}", self.path.display()))?;
+        self.last_saved = Some(current_time);
+        Ok(())
+    }
+
+    /// Saves the session if it hasn't been for a while.
+    pub fn save_if_due(
+        &mut self,
+        scheduler: &SchedulePlanner,
+        current_time: DateTime<Utc>,
+    ) -> Result<()> {
+        let due = match self.last_saved {
+            Some(last_saved) => {
+                current_time - last_saved >= Duration::seconds(SAVE_INTERVAL_SECONDS)
+            }
+            None => true,
+        };
+        if due {
+            self.save(scheduler, current_time)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn remove(&self) -> Result<()> {
+        if self.path.exists() {
+            fs::remove_file(&self.path)
+                .with_context(|| format!("Unable to remove {}", self.path.display()))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+//! Where ppcli keeps its files, and its own settings from `config.toml` in the
+//! config directory.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// The directory name used under the per-user data and config directories.
+const APP_DIR_NAME: &str = "practice-planner";
+const SETTINGS_FILE: &str = "config.toml";
+/// Where data was saved before the per-user data directory was used.
+const LEGACY_DATA_DIR: &str = "./saved_data";
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    /// Overrides the per-user data directory
+    pub data_dir: Option<PathBuf>,
+    /// Shell commands to run on practice session events
+    pub hooks: Hooks,
+}
+
+/// Shell commands run on practice session events, from the `[hooks]` table.
+/// Each is named after the event that runs it.
+#[derive(Deserialize, Default, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct Hooks {
+    pub session_started: Option<String>,
+    pub skill_started: Option<String>,
+    pub skill_ended: Option<String>,
+    pub exercise_changed: Option<String>,
+    pub paused: Option<String>,
+    pub resumed: Option<String>,
+    pub rest_started: Option<String>,
+    pub rest_ended: Option<String>,
+    pub session_completed: Option<String>,
+}
+
+impl Hooks {
+    /// The command for an event, by its `SessionEvent::name`.
+    pub fn command(&self, event_name: &str) -> Option<&str> {
+        let command = match event_name {
+            "session_started" => &self.session_started,
+            "skill_started" => &self.skill_started,
+            "skill_ended" => &self.skill_ended,
+            "exercise_changed" => &self.exercise_changed,
+            "paused" => &self.paused,
+            "resumed" => &self.resumed,
+            "rest_started" => &self.rest_started,
+            "rest_ended" => &self.rest_ended,
+            "session_completed" => &self.session_completed,
+            _ => return None,
+        };
+        command.as_deref()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        [
+            &self.session_started,
+            &self.skill_started,
+            &self.skill_ended,
+            &self.exercise_changed,
+            &self.paused,
+            &self.resumed,
+            &self.rest_started,
+            &self.rest_ended,
+            &self.session_completed,
+        ]
+        .iter()
+        .all(|command| command.is_none())
+    }
+}
+
+impl Settings {
+    /// Reads the settings in `config_dir`, using the defaults if there are
+    /// none.
+    pub fn load(config_dir: &Path) -> Result<Self> {
+        let path = config_dir.join(SETTINGS_FILE);
+        if !path.exists() {
+            return Ok(Settings::default());
+        }
+
+        let contents = fs::read_to_string(&path)?;
+        toml::from_str(&contents).with_context(|| format!("Invalid settings in {}", path.display()))
+    }
+}
+
+/// The per-user config directory, e.g. `~/.config/practice-planner`.
+pub fn default_config_dir() -> Result<PathBuf> {
+    dirs::config_dir()
+        .map(|dir| dir.join(APP_DIR_NAME))
+        .context("Unable to find the config directory, use --config-dir")
+}
+
+/// The per-user data directory, e.g. `~/.local/share/practice-planner`.
+pub fn default_data_dir() -> Result<PathBuf> {
+    dirs::data_dir()
+        .map(|dir| dir.join(APP_DIR_NAME))
+        .context("Unable to find the data directory, use --data-dir")
+}
+
+/// Creates the data directory if this is the first run, pointing out data
+/// left in `./saved_data` by older versions.
+pub fn create_data_dir(data_dir: &Path) -> Result<()> {
+    if data_dir.exists() {
+        return Ok(());
+    }
+
+    let legacy_dir = Path::new(LEGACY_DATA_DIR);
+    if legacy_dir.exists() && legacy_dir != data_dir {
+        eprintln!(
+            "Found saved data in {}, move it to {} or use --data-dir {} to keep using it",
+            legacy_dir.display(),
+            data_dir.display(),
+            legacy_dir.display()
+        );
+    }
+
+    fs::create_dir_all(data_dir)
+        .with_context(|| format!("Unable to create the data directory {}", data_dir.display()))
+}
@@ -0,0 +1,270 @@
+//! Interactive terminal practice screen.
+
+use std::io::{self, Stdout, Write};
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+use pplib::{AlertListener, AlertSink, BellAlertSink, SchedulePlanner, SessionState};
+
+use crate::hooks::CommandHook;
+use crate::render::render_exercise;
+use crate::session_file::SessionFile;
+use crate::settings::Hooks;
+
+const KEY_HELP: [&str; 2] = [
+    "[space] pause/resume  [n]ext / [p]revious exercise  [s]kip / [b]ack a skill  [q]uit",
+    "[+] 5 more minutes  [up/down] select an upcoming skill  [u]/[d] practice it earlier/later",
+];
+
+/// How much time `+` adds to the current skill.
+const EXTEND_MINUTES: i64 = 5;
+
+/// How the practice screen was left.
+pub enum PracticeOutcome {
+    Completed,
+    Stopped,
+}
+
+fn format_time_left(time_left: Duration) -> String {
+    let secs = time_left.num_seconds().max(0);
+    format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        (secs % 3600) / 60,
+        secs % 60
+    )
+}
+
+/// Plays the ding sound when built with it, falling back to the terminal bell.
+fn alert_sink() -> Box<dyn AlertSink> {
+    #[cfg(feature = "wav-alerts")]
+    match pplib::WavAlertSink::new() {
+        Ok(sink) => return Box::new(sink),
+        Err(e) => eprintln!("Unable to play sounds, using the terminal bell: {}", e),
+    }
+
+    Box::new(BellAlertSink)
+}
+
+/// Runs today's practice session in the terminal until it is completed or
+/// stopped, running `hooks` on its events.
+pub fn practice(
+    scheduler: &mut SchedulePlanner,
+    hooks: &Hooks,
+    session_file: &mut SessionFile,
+) -> Result<PracticeOutcome> {
+    scheduler.add_session_listener(Box::new(AlertListener::new(alert_sink())));
+    if !hooks.is_empty() {
+        scheduler.add_session_listener(Box::new(CommandHook::new(hooks.clone())));
+    }
+    // a resumed session is already underway, on pause
+    if !scheduler.practicing {
+        scheduler.start_daily_practice(Utc::now())?;
+    }
+
+    let mut stdout = io::stdout();
+    terminal::enable_raw_mode()?;
+    execute!(stdout, EnterAlternateScreen, Hide)?;
+    let outcome = practice_loop(scheduler, session_file, &mut stdout);
+    execute!(stdout, Show, LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+
+    // the session is only left behind to resume if ppcli didn't get to here
+    if outcome.is_ok() {
+        session_file.remove()?;
+    }
+    outcome
+}
+
+fn practice_loop(
+    scheduler: &mut SchedulePlanner,
+    session_file: &mut SessionFile,
+    stdout: &mut Stdout,
+) -> Result<PracticeOutcome> {
+    // the upcoming skill chosen for reordering, by its schedule position
+    let mut selected: Option<usize> = None;
+    let mut last_state = None;
+    loop {
+        let now = Utc::now();
+        let state = scheduler.tick_practice_session(now)?;
+        if state == SessionState::Finished {
+            return Ok(PracticeOutcome::Completed);
+        }
+        if last_state != Some(state) {
+            session_file.save(scheduler, now)?;
+            last_state = Some(state);
+        } else {
+            session_file.save_if_due(scheduler, now)?;
+        }
+
+        let session = scheduler.practice_session.as_ref().unwrap();
+        let upcoming = session.movable_skills();
+        selected = selected.filter(|idx| upcoming.contains(idx));
+        draw(scheduler, selected, stdout)?;
+
+        if !event::poll(std::time::Duration::from_millis(200))? {
+            continue;
+        }
+        if let Event::Key(KeyEvent { code, modifiers }) = event::read()? {
+            let session = scheduler.practice_session.as_ref().unwrap();
+            let (paused, resting) = (session.is_paused(), session.is_resting());
+            match code {
+                // a rest break is already a pause, and can only be cut short
+                KeyCode::Char(' ') | KeyCode::Char('+') if resting => {}
+                KeyCode::Char(' ') if paused => scheduler.resume_practice_session(Utc::now())?,
+                KeyCode::Char(' ') => scheduler.pause_practice_session(Utc::now())?,
+                KeyCode::Char('n') | KeyCode::Right => scheduler.next_exercise(Utc::now())?,
+                KeyCode::Char('p') | KeyCode::Left => scheduler.previous_exercise(Utc::now())?,
+                KeyCode::Char('+') => {
+                    scheduler.extend_skill(Duration::minutes(EXTEND_MINUTES), Utc::now())?
+                }
+                KeyCode::Char('s') => {
+                    scheduler.skip_skill(Utc::now())?;
+                    if !scheduler.practicing {
+                        return Ok(PracticeOutcome::Completed);
+                    }
+                }
+                KeyCode::Char('b') if upcoming.start > 1 => scheduler.go_back_skill(Utc::now())?,
+                KeyCode::Up => {
+                    selected = match selected {
+                        Some(idx) if idx > upcoming.start => Some(idx - 1),
+                        Some(idx) => Some(idx),
+                        None => Some(upcoming.end - 1),
+                    }
+                    .filter(|idx| upcoming.contains(idx));
+                }
+                KeyCode::Down => {
+                    selected = match selected {
+                        Some(idx) if idx + 1 < upcoming.end => Some(idx + 1),
+                        Some(idx) => Some(idx),
+                        None => Some(upcoming.start),
+                    }
+                    .filter(|idx| upcoming.contains(idx));
+                }
+                KeyCode::Char('u') => {
+                    if let Some(idx) = selected.filter(|idx| *idx > upcoming.start) {
+                        scheduler.move_remaining_skill(idx, idx - 1)?;
+                        selected = Some(idx - 1);
+                    }
+                }
+                KeyCode::Char('d') => {
+                    if let Some(idx) = selected.filter(|idx| idx + 1 < upcoming.end) {
+                        scheduler.move_remaining_skill(idx, idx + 1)?;
+                        selected = Some(idx + 1);
+                    }
+                }
+                KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    scheduler.stop_practicing()?;
+                    return Ok(PracticeOutcome::Stopped);
+                }
+                KeyCode::Char('q') | KeyCode::Esc => {
+                    scheduler.stop_practicing()?;
+                    return Ok(PracticeOutcome::Stopped);
+                }
+                _ => {}
+            }
+            session_file.save(scheduler, Utc::now())?;
+        }
+    }
+}
+
+fn draw(scheduler: &SchedulePlanner, selected: Option<usize>, stdout: &mut Stdout) -> Result<()> {
+    let session = scheduler.practice_session.as_ref().unwrap();
+    let current_idx = session.get_current_skill_idx();
+    let rounds = session.rounds();
+    let (cols, rows) = terminal::size()?;
+
+    queue!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
+    queue!(
+        stdout,
+        SetAttribute(Attribute::Bold),
+        Print("Today's Practice\r\n"),
+        SetAttribute(Attribute::Reset)
+    )?;
+    for (idx, skill) in session.schedule.iter().enumerate() {
+        let marker = if idx == current_idx {
+            " > "
+        } else if idx < current_idx && session.is_skipped(idx) {
+            "[-]"
+        } else if idx < current_idx {
+            "[x]"
+        } else {
+            "[ ]"
+        };
+        let label = match session.is_segment(&skill.skill_name) {
+            true if idx == 0 => format!("Warm-up: {}", skill),
+            true => format!("Cool-down: {}", skill),
+            false if rounds > 1 => {
+                format!("{} (round {}/{})", skill, session.round_of(idx) + 1, rounds)
+            }
+            false => skill.to_string(),
+        };
+        if selected == Some(idx) {
+            queue!(
+                stdout,
+                Print(format!("{} ", marker)),
+                SetAttribute(Attribute::Reverse),
+                Print(label),
+                SetAttribute(Attribute::Reset),
+                Print("\r\n")
+            )?;
+        } else {
+            queue!(stdout, Print(format!("{} {}\r\n", marker, label)))?;
+        }
+    }
+
+    let status = if session.is_paused() {
+        "  (paused)"
+    } else {
+        ""
+    };
+    let timer = if session.is_resting() {
+        format!(
+            "Rest break: {}, then {}. [s] to start now",
+            format_time_left(session.time_left),
+            session.current_skill
+        )
+    } else if session.time_over > Duration::seconds(0) {
+        format!(
+            "{}  Overtime: +{}, [s] to move on",
+            session.current_skill,
+            format_time_left(session.time_over)
+        )
+    } else {
+        format!(
+            "{}  Time left: {}",
+            session.current_skill,
+            format_time_left(session.time_left)
+        )
+    };
+    queue!(
+        stdout,
+        Print("\r\n"),
+        SetAttribute(Attribute::Bold),
+        Print(format!("{}{}\r\n", timer, status)),
+        SetAttribute(Attribute::Reset),
+        Print(format!("{}\r\n{}\r\n\r\n", KEY_HELP[0], KEY_HELP[1]))
+    )?;
+
+    // leave room for the lines above
+    let used_rows = session.schedule.len() + 6;
+    let available_rows = (rows as usize).saturating_sub(used_rows);
+    match &session.current_exercise {
+        Some(exercise) => {
+            queue!(stdout, Print(format!("{}\r\n", exercise.exercise_name)))?;
+            let lines = render_exercise(&exercise.exercise_markdown_contents, cols as usize, true);
+            for line in lines.iter().take(available_rows.saturating_sub(1)) {
+                queue!(stdout, Print(format!("{}\r\n", line)))?;
+            }
+        }
+        None => queue!(stdout, Print("No exercises for this skill\r\n"))?,
+    }
+
+    stdout.flush()?;
+    Ok(())
+}
@@ -0,0 +1,133 @@
+//! Alerts played when a practice session moves on, whatever the frontend.
+
+use std::fmt::Debug;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+
+use crate::{SessionEvent, SessionListener};
+
+/// The moments in a practice session that are worth an alert.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Alert {
+    /// The timer ran out and the next skill started.
+    SkillChanged,
+    /// The timer ran out and a rest break started.
+    RestStarted,
+    /// The last skill was finished.
+    SessionCompleted,
+}
+
+/// Somewhere to send session alerts, such as a speaker or the terminal.
+pub trait AlertSink: Debug {
+    fn alert(&mut self, alert: Alert) -> Result<()>;
+}
+
+/// Plays an alert sink's alerts as the session moves on, subscribed with
+/// `SchedulePlanner::add_session_listener`.
+#[derive(Debug)]
+pub struct AlertListener {
+    sink: Box<dyn AlertSink>,
+}
+
+impl AlertListener {
+    pub fn new(sink: Box<dyn AlertSink>) -> Self {
+        AlertListener { sink }
+    }
+}
+
+impl SessionListener for AlertListener {
+    fn on_event(&mut self, event: &SessionEvent) -> Result<()> {
+        match event {
+            // the first skill starts with the session, which needs no alert
+            SessionEvent::SkillStarted { skill_index, .. } if *skill_index > 0 => {
+                self.sink.alert(Alert::SkillChanged)
+            }
+            SessionEvent::RestStarted { .. } => self.sink.alert(Alert::RestStarted),
+            SessionEvent::SessionCompleted { .. } => self.sink.alert(Alert::SessionCompleted),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Plays nothing, but keeps the alerts it received so tests can check them.
+#[derive(Clone, Default, Debug)]
+pub struct NullAlertSink {
+    alerts: Arc<Mutex<Vec<Alert>>>,
+}
+
+impl NullAlertSink {
+    /// The alerts received so far, shared by every clone of this sink.
+    pub fn alerts(&self) -> Vec<Alert> {
+        self.alerts.lock().unwrap().clone()
+    }
+}
+
+impl AlertSink for NullAlertSink {
+    fn alert(&mut self, alert: Alert) -> Result<()> {
+        self.alerts.lock().unwrap().push(alert);
+        Ok(())
+    }
+}
+
+/// Rings the terminal bell.
+#[derive(Default, Debug)]
+pub struct BellAlertSink;
+
+impl AlertSink for BellAlertSink {
+    fn alert(&mut self, _alert: Alert) -> Result<()> {
+        let mut stdout = io::stdout();
+        stdout.write_all(b"\x07")?;
+        stdout.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "wav-alerts")]
+pub use wav::WavAlertSink;
+
+#[cfg(feature = "wav-alerts")]
+mod wav {
+    use std::fmt;
+    use std::io::Cursor;
+
+    use anyhow::Result;
+    use rodio::{Decoder, OutputStream, OutputStreamHandle, Source};
+
+    use super::{Alert, AlertSink};
+
+    const DING_WAV: &[u8] = include_bytes!("../../audio/ding.wav");
+
+    /// Plays `audio/ding.wav` on the default sound device.
+    pub struct WavAlertSink {
+        // the stream stops playing when dropped
+        _stream: OutputStream,
+        stream_handle: OutputStreamHandle,
+    }
+
+    impl WavAlertSink {
+        pub fn new() -> Result<Self> {
+            let (stream, stream_handle) = OutputStream::try_default()?;
+            Ok(WavAlertSink {
+                _stream: stream,
+                stream_handle,
+            })
+        }
+    }
+
+    impl fmt::Debug for WavAlertSink {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("WavAlertSink").finish_non_exhaustive()
+        }
+    }
+
+    impl AlertSink for WavAlertSink {
+        fn alert(&mut self, _alert: Alert) -> Result<()> {
+            let source = Decoder::new(Cursor::new(DING_WAV))?;
+            // plays on the stream's own thread, so this doesn't block
+            self.stream_handle.play_raw(source.convert_samples())?;
+            Ok(())
+        }
+    }
+}
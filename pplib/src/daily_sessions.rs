@@ -0,0 +1,62 @@
+//! Several practice sessions on one day.
+//!
+//! Completing a session clears today's schedule, so the next one picked is
+//! for another session that day. With `exclude_practiced_today` set, it
+//! leaves out the skills already practiced in today's earlier sessions.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Bound;
+
+use anyhow::Result;
+use chrono::{Date, DateTime, Duration, Utc};
+
+use crate::{PracticeRecord, SchedulePlanner};
+
+/// One day's practice sessions, oldest first, along with when each was
+/// completed.
+pub type DaySessions<'a> = Vec<(DateTime<Utc>, &'a PracticeRecord)>;
+
+impl SchedulePlanner {
+    /// The practice sessions completed on the day of `current_time`, oldest
+    /// first.
+    pub fn todays_sessions(
+        &self,
+        current_time: DateTime<Utc>,
+    ) -> impl DoubleEndedIterator<Item = (&DateTime<Utc>, &PracticeRecord)> {
+        let today = current_time.date().naive_utc();
+        self.history_between(today, today)
+    }
+
+    /// The names of the skills practiced in today's sessions so far.
+    pub fn skills_practiced_today(&self, current_time: DateTime<Utc>) -> BTreeSet<String> {
+        self.todays_sessions(current_time)
+            .flat_map(|(_, record)| record.skills.iter().map(|s| s.skill_name.clone()))
+            .collect()
+    }
+
+    /// The practice sessions of the last n days, grouped by day. Unlike
+    /// `get_history_n_days_back`, a day's sessions are kept apart, oldest
+    /// first.
+    pub fn sessions_n_days_back(
+        &self,
+        n: usize,
+        current_time: DateTime<Utc>,
+    ) -> Result<BTreeMap<Date<Utc>, DaySessions<'_>>> {
+        let n_days_back = current_time
+            .checked_sub_signed(Duration::days(n.try_into()?))
+            .ok_or_else(|| anyhow::anyhow!("Invalid historical search term"))?;
+
+        let mut sessions: BTreeMap<Date<Utc>, DaySessions<'_>> = BTreeMap::new();
+        for (time, record) in self
+            .history
+            .range((Bound::Excluded(n_days_back), Bound::Unbounded))
+        {
+            sessions
+                .entry(time.date())
+                .or_default()
+                .push((*time, record));
+        }
+
+        Ok(sessions)
+    }
+}
@@ -0,0 +1,134 @@
+//! Exercises as a single text document for editing outside the apps, with the
+//! exercise's name in a front matter block above its markdown:
+//!
+//! ```text
+//! ---
+//! name: Major Scales
+//! ---
+//!
+//! Play each major scale in one position...
+//! ```
+
+use crate::{
+    split_vextab_blocks, validate_vextab, ExerciseBlock, PracticeExercise, SchedulerError,
+};
+
+const FRONT_MATTER_DELIMITER: &str = "---";
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ExerciseDocument {
+    pub exercise_name: String,
+    pub markdown: String,
+}
+
+impl ExerciseDocument {
+    pub fn from_exercise(exercise: &PracticeExercise) -> Self {
+        ExerciseDocument {
+            exercise_name: exercise.exercise_name.clone(),
+            markdown: exercise.exercise_markdown_contents.clone(),
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        format!(
+            "{delimiter}\nname: {}\n{delimiter}\n\n{}",
+            self.exercise_name,
+            self.markdown,
+            delimiter = FRONT_MATTER_DELIMITER
+        )
+    }
+
+    /// Parses a document written by `to_text`, checking its front matter and
+    /// the vextab blocks in its markdown. Line numbers in errors count from
+    /// the top of the document.
+    pub fn parse(text: &str) -> Result<Self, SchedulerError> {
+        let mut lines = text.lines();
+        if lines.next().map(str::trim_end) != Some(FRONT_MATTER_DELIMITER) {
+            return Err(SchedulerError::InvalidFrontMatter(format!(
+                "the document must start with a {} line",
+                FRONT_MATTER_DELIMITER
+            )));
+        }
+
+        let mut exercise_name = None;
+        let mut front_matter_lines = 1;
+        let mut closed = false;
+        for line in lines.by_ref() {
+            front_matter_lines += 1;
+            let line = line.trim_end();
+            if line == FRONT_MATTER_DELIMITER {
+                closed = true;
+                break;
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (key, value) = line.split_once(':').ok_or_else(|| {
+                SchedulerError::InvalidFrontMatter(format!("expected key: value, got {:?}", line))
+            })?;
+            match key.trim() {
+                "name" => exercise_name = Some(value.trim().to_string()),
+                key => {
+                    return Err(SchedulerError::InvalidFrontMatter(format!(
+                        "unknown key {:?}",
+                        key
+                    )))
+                }
+            }
+        }
+
+        if !closed {
+            return Err(SchedulerError::InvalidFrontMatter(format!(
+                "missing the closing {} line",
+                FRONT_MATTER_DELIMITER
+            )));
+        }
+        let exercise_name = exercise_name
+            .ok_or_else(|| SchedulerError::InvalidFrontMatter("missing the name".to_string()))?;
+
+        // the blank line after the front matter is part of the layout
+        let markdown = lines.collect::<Vec<_>>().join("\n");
+        let (markdown, lines_before) = match markdown.strip_prefix('\n') {
+            Some(markdown) => (markdown, front_matter_lines + 1),
+            None => (markdown.as_str(), front_matter_lines),
+        };
+        validate_exercise_markdown(markdown).map_err(|e| match e {
+            SchedulerError::InvalidVextab { line, message } => SchedulerError::InvalidVextab {
+                line: line + lines_before,
+                message,
+            },
+            e => e,
+        })?;
+
+        Ok(ExerciseDocument {
+            exercise_name,
+            markdown: format!("{}\n", markdown.trim_end()),
+        })
+    }
+}
+
+/// Checks every vextab block in exercise markdown. Line numbers in errors
+/// count from the top of the markdown.
+pub fn validate_exercise_markdown(markdown: &str) -> Result<(), SchedulerError> {
+    let mut lines_before = 0;
+    for block in split_vextab_blocks(markdown) {
+        match block {
+            ExerciseBlock::Markdown(text) => lines_before += text.matches('\n').count(),
+            ExerciseBlock::Vextab(source) => {
+                validate_vextab(&source).map_err(|e| match e {
+                    SchedulerError::InvalidVextab { line, message } => {
+                        SchedulerError::InvalidVextab {
+                            line: line + lines_before,
+                            message,
+                        }
+                    }
+                    e => e,
+                })?;
+                lines_before += source.matches('\n').count();
+            }
+        }
+    }
+
+    Ok(())
+}
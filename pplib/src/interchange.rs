@@ -0,0 +1,74 @@
+//! A JSON file format for moving a profile's configuration and history between
+//! the webapp and ppcli.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{PlannerConfiguration, PracticeHistory, SchedulePlanner, SchedulerError};
+
+/// Bumped whenever the export format changes incompatibly.
+pub const EXPORT_FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PlannerExport {
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub config: PlannerConfiguration,
+    pub history: PracticeHistory,
+}
+
+/// Just enough of an export to check its version before reading the rest.
+#[derive(Deserialize)]
+struct ExportHeader {
+    format_version: u32,
+}
+
+impl SchedulePlanner {
+    pub fn export(&self, current_time: DateTime<Utc>) -> PlannerExport {
+        PlannerExport {
+            format_version: EXPORT_FORMAT_VERSION,
+            exported_at: current_time,
+            config: self.config.clone(),
+            history: self.history.clone(),
+        }
+    }
+
+    pub fn export_json(&self, current_time: DateTime<Utc>) -> Result<String, SchedulerError> {
+        serde_json::to_string_pretty(&self.export(current_time))
+            .map_err(|e| SchedulerError::Other(e.into()))
+    }
+
+    /// Replaces the configuration with an exported one, and merges in its
+    /// history so practice recorded on either side is kept. Returns the number
+    /// of practice sessions added to the history.
+    pub fn import(&mut self, export: PlannerExport) -> Result<usize, SchedulerError> {
+        if export.format_version > EXPORT_FORMAT_VERSION {
+            return Err(SchedulerError::UnsupportedExportVersion(
+                export.format_version,
+            ));
+        }
+
+        let history_len = self.history.len();
+        self.config = export.config;
+        self.history.extend(export.history);
+        // today's schedule may refer to skills that no longer exist
+        self.todays_schedule = None;
+        self.todays_schedule_time = None;
+
+        Ok(self.history.len() - history_len)
+    }
+
+    pub fn import_json(&mut self, json: &str) -> Result<usize, SchedulerError> {
+        let header: ExportHeader =
+            serde_json::from_str(json).map_err(|e| SchedulerError::InvalidExport(e.to_string()))?;
+        if header.format_version > EXPORT_FORMAT_VERSION {
+            return Err(SchedulerError::UnsupportedExportVersion(
+                header.format_version,
+            ));
+        }
+
+        let export: PlannerExport =
+            serde_json::from_str(json).map_err(|e| SchedulerError::InvalidExport(e.to_string()))?;
+        self.import(export)
+    }
+}
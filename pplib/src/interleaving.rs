@@ -0,0 +1,81 @@
+//! Interleaved practice, which cycles through the session's skills in
+//! several short rounds instead of practicing each in one long block, such
+//! as 4 skills in 3 rounds of 5 minutes each.
+//!
+//! Only the scheduled skills are repeated. The warm-up and cool-down still
+//! come once, at the start and end, and the history records each skill once
+//! with its time totalled over the rounds.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+
+use crate::{PracticeSession, PracticeSkill, SchedulePlanner, SchedulerError};
+
+#[serde_with::serde_as]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Interleaving {
+    /// How many times the skills are cycled through.
+    pub rounds: usize,
+    /// How long each skill is practiced in each round.
+    #[serde_as(as = "serde_with::DurationSeconds<i64>")]
+    pub block_time: Duration,
+}
+
+impl PracticeSession {
+    /// How many rounds the session cycles through its skills, which is 1
+    /// unless practice is interleaved.
+    pub fn rounds(&self) -> usize {
+        let mut counts = BTreeMap::new();
+        for skill in self.schedule.iter() {
+            *counts.entry(&skill.skill_name).or_insert(0) += 1;
+        }
+
+        counts.into_values().max().unwrap_or(1)
+    }
+}
+
+impl SchedulePlanner {
+    /// Sets how many rounds practice is interleaved in, or goes back to
+    /// practicing each skill in one block with `None`.
+    pub fn set_interleaving(
+        &mut self,
+        interleaving: Option<Interleaving>,
+    ) -> Result<(), SchedulerError> {
+        if let Some(interleaving) = &interleaving {
+            if interleaving.rounds < 2 {
+                return Err(SchedulerError::InvalidInterleaving(
+                    "there must be at least two rounds".to_string(),
+                ));
+            }
+            if interleaving.block_time <= Duration::seconds(0) {
+                return Err(SchedulerError::InvalidInterleaving(
+                    "each round must last for some time".to_string(),
+                ));
+            }
+        }
+
+        self.config.interleaving = interleaving;
+        Ok(())
+    }
+
+    /// How long a session practices each skill at a time.
+    pub(crate) fn session_practice_time(&self) -> Duration {
+        match &self.config.interleaving {
+            Some(interleaving) => interleaving.block_time,
+            None => self.config.skill_practice_time,
+        }
+    }
+
+    /// Repeats the scheduled skills once for every round.
+    pub(crate) fn interleave(&self, schedule: Vec<Arc<PracticeSkill>>) -> Vec<Arc<PracticeSkill>> {
+        match &self.config.interleaving {
+            Some(interleaving) => (0..interleaving.rounds)
+                .flat_map(|_| schedule.iter().cloned())
+                .collect(),
+            None => schedule,
+        }
+    }
+}
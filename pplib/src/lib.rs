@@ -3,23 +3,49 @@ extern crate lazy_static;
 
 use std;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashSet;
 use std::fmt;
-use std::fs::File;
-use std::io::prelude::*;
-use std::ops::Sub;
+use std::ops::{Range, Sub};
 use std::sync::Arc;
 
 use anyhow::Result;
-use chrono::{Date, DateTime, Duration, Utc};
+use chrono::{Date, DateTime, Duration, NaiveDate, Utc};
 use log;
 use rand::prelude::*;
 use rand::seq::SliceRandom;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+mod alerts;
+pub use crate::alerts::*;
 mod constants;
 use crate::constants::*;
+mod daily_sessions;
+pub use crate::daily_sessions::*;
+mod exercise_document;
+pub use crate::exercise_document::*;
+mod interchange;
+pub use crate::interchange::*;
+mod interleaving;
+pub use crate::interleaving::*;
+mod reports;
+pub use crate::reports::*;
+mod rest_breaks;
+pub use crate::rest_breaks::*;
+mod saved_session;
+pub use crate::saved_session::*;
+mod profiles;
+pub use crate::profiles::*;
+mod schedule_edits;
+mod segments;
+pub use crate::segments::*;
+mod session_events;
+pub use crate::session_events::*;
+mod storage;
+pub use crate::storage::*;
+mod vextab;
+pub use crate::vextab::*;
 
 #[derive(Error, Debug)]
 pub enum SchedulerError {
@@ -31,10 +57,97 @@ pub enum SchedulerError {
     #[error("You must have at least 4 skills to practice")]
     MissingSkills(),
 
+    #[error("No skill named {0:?}")]
+    UnknownSkill(String),
+
+    #[error("No exercise named {0:?}")]
+    UnknownExercise(String),
+
+    #[error("A skill named {0:?} already exists")]
+    SkillExists(String),
+
+    #[error("An exercise named {0:?} already exists")]
+    ExerciseExists(String),
+
+    #[error("Invalid name {0:?}")]
+    InvalidName(String),
+
+    #[error("A profile named {0:?} already exists")]
+    ProfileExists(String),
+
+    #[error("No profile named {0:?}")]
+    UnknownProfile(String),
+
+    #[error("Invalid profile name {0:?}")]
+    InvalidProfileName(String),
+
+    #[error("The active profile can not be removed")]
+    ActiveProfileRemoval(),
+
+    #[error("The default profile can not be removed")]
+    DefaultProfileRemoval(),
+
+    #[error("Invalid vextab on line {line}: {message}")]
+    InvalidVextab { line: usize, message: String },
+
+    #[error("Invalid front matter: {0}")]
+    InvalidFrontMatter(String),
+
+    #[error("Invalid warm-up or cool-down: {0}")]
+    InvalidSegment(String),
+
+    #[error("Invalid rest breaks: {0}")]
+    InvalidRestBreaks(String),
+
+    #[error("Invalid interleaving: {0}")]
+    InvalidInterleaving(String),
+
+    #[error("Unable to log practice: {0}")]
+    InvalidPracticeLog(String),
+
+    #[error("Unable to change today's schedule: {0}")]
+    InvalidScheduleEdit(String),
+
+    #[error("Invalid export file: {0}")]
+    InvalidExport(String),
+
+    #[error("Export file version {0} is newer than this version supports")]
+    UnsupportedExportVersion(u32),
+
     #[error(transparent)]
     Other(#[from] anyhow::Error), // source and Display delegate to anyhow::Error
 }
 
+impl SchedulerError {
+    /// The name of the variant, which stays the same for machine-readable
+    /// output even if the message changes.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SchedulerError::MissingSkills() => "MissingSkills",
+            SchedulerError::UnknownSkill(_) => "UnknownSkill",
+            SchedulerError::UnknownExercise(_) => "UnknownExercise",
+            SchedulerError::SkillExists(_) => "SkillExists",
+            SchedulerError::ExerciseExists(_) => "ExerciseExists",
+            SchedulerError::InvalidName(_) => "InvalidName",
+            SchedulerError::ProfileExists(_) => "ProfileExists",
+            SchedulerError::UnknownProfile(_) => "UnknownProfile",
+            SchedulerError::InvalidProfileName(_) => "InvalidProfileName",
+            SchedulerError::ActiveProfileRemoval() => "ActiveProfileRemoval",
+            SchedulerError::DefaultProfileRemoval() => "DefaultProfileRemoval",
+            SchedulerError::InvalidVextab { .. } => "InvalidVextab",
+            SchedulerError::InvalidFrontMatter(_) => "InvalidFrontMatter",
+            SchedulerError::InvalidSegment(_) => "InvalidSegment",
+            SchedulerError::InvalidRestBreaks(_) => "InvalidRestBreaks",
+            SchedulerError::InvalidInterleaving(_) => "InvalidInterleaving",
+            SchedulerError::InvalidPracticeLog(_) => "InvalidPracticeLog",
+            SchedulerError::InvalidScheduleEdit(_) => "InvalidScheduleEdit",
+            SchedulerError::InvalidExport(_) => "InvalidExport",
+            SchedulerError::UnsupportedExportVersion(_) => "UnsupportedExportVersion",
+            SchedulerError::Other(_) => "Other",
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Hash, PartialOrd, Ord, PartialEq, Eq, Debug)]
 pub struct PracticeExercise {
     pub exercise_name: String,
@@ -64,22 +177,145 @@ pub struct PlannerConfiguration {
     /// The number of skills to practice per day.
     pub skills_per_day: usize,
     pub skills: Vec<Arc<PracticeSkill>>,
+    /// Skills in the trash. They are not scheduled or shown in the settings,
+    /// but their history is kept and they can be restored.
+    #[serde(default)]
+    pub archived_skills: Vec<Arc<PracticeSkill>>,
+    /// Exercises in the trash.
+    #[serde(default)]
+    pub archived_exercises: Vec<ArchivedExercise>,
+    /// Whether a skill's timer keeps counting past zero until the session is
+    /// advanced by hand, instead of moving on when its time runs out.
+    #[serde(default)]
+    pub overtime: bool,
+    /// Practiced at the start of every session.
+    #[serde(default)]
+    pub warm_up: Option<PracticeSegment>,
+    /// Practiced at the end of every session.
+    #[serde(default)]
+    pub cool_down: Option<PracticeSegment>,
+    /// How often to rest between skills, if at all.
+    #[serde(default)]
+    pub rest_breaks: Option<RestBreaks>,
+    /// Whether a later session on the same day leaves out the skills already
+    /// practiced that day.
+    #[serde(default)]
+    pub exclude_practiced_today: bool,
+    /// How many rounds to cycle through the skills in, if practice is
+    /// interleaved.
+    #[serde(default)]
+    pub interleaving: Option<Interleaving>,
+}
+
+/// An exercise in the trash, along with the skill it was removed from.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct ArchivedExercise {
+    pub skill_name: String,
+    pub exercise: Arc<PracticeExercise>,
+}
+
+/// A completed practice session as stored in history.
+#[serde_with::serde_as]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct PracticeRecord {
+    pub skills: Vec<Arc<PracticeSkill>>,
+    /// Free-text journal notes, keyed by skill name.
+    pub notes: BTreeMap<String, String>,
+    /// How long each skill was practiced, keyed by skill name. Missing for
+    /// sessions recorded before durations were kept.
+    #[serde(default)]
+    #[serde_as(as = "BTreeMap<_, serde_with::DurationSeconds<i64>>")]
+    pub durations: BTreeMap<String, Duration>,
+    /// Names of the skills that were skipped before their time ran out, in
+    /// every round they were practiced in.
+    #[serde(default)]
+    pub skipped: BTreeSet<String>,
+}
+
+/// History saved before notes existed was just the list of skills practiced.
+impl From<Vec<Arc<PracticeSkill>>> for PracticeRecord {
+    fn from(skills: Vec<Arc<PracticeSkill>>) -> Self {
+        PracticeRecord {
+            skills,
+            notes: BTreeMap::new(),
+            durations: BTreeMap::new(),
+            skipped: BTreeSet::new(),
+        }
+    }
+}
+
+/// Historical practice sessions, keyed by the time they were completed.
+pub type PracticeHistory = BTreeMap<DateTime<Utc>, PracticeRecord>;
+
+/// A journal note attached to a skill in a historical practice session.
+#[derive(Clone, PartialEq, Debug)]
+pub struct JournalNote {
+    pub time: DateTime<Utc>,
+    pub skill_name: String,
+    pub note: String,
 }
 
 #[derive(Debug)]
 pub struct SchedulePlanner {
     pub config: PlannerConfiguration,
     /// BTreeMap containing historical practice sessions.
-    pub history: BTreeMap<DateTime<Utc>, Vec<Arc<PracticeSkill>>>,
+    pub history: PracticeHistory,
     pub todays_schedule: Option<Vec<Arc<PracticeSkill>>>,
+    /// When today's schedule was picked, so a stale schedule is replaced
+    /// on a new day.
+    pub todays_schedule_time: Option<DateTime<Utc>>,
+    /// Names of the skills kept in today's schedule when it is refilled.
+    pub todays_pinned: BTreeSet<String>,
+    /// Names of the skills left out of today's schedule.
+    pub todays_excluded: BTreeSet<String>,
     /// Whether a practice session is currently underway
     pub practicing: bool,
     /// The in-progress practice session
     pub practice_session: Option<PracticeSession>,
+    /// Told about each practice session event
+    session_listeners: Vec<Box<dyn SessionListener>>,
 }
 
-/// Practice sessions. If one exists, it is active.
-#[derive(Debug)]
+/// Where a practice session's timer is at.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SessionState {
+    NotStarted,
+    Running,
+    Paused,
+    /// The current skill's time ran out, and the session should advance.
+    SkillComplete,
+    /// Taking a rest break before the current skill starts.
+    Resting,
+    /// The rest break ran out, and the current skill should start.
+    RestComplete,
+    /// The last skill was completed.
+    Finished,
+}
+
+/// Times kept for each round of a practice session's skills, keyed by skill
+/// name and listed by round.
+type RoundTimes = BTreeMap<String, Vec<Duration>>;
+
+fn round_time(times: &RoundTimes, skill_name: &str, round: usize) -> Duration {
+    times
+        .get(skill_name)
+        .and_then(|rounds| rounds.get(round))
+        .cloned()
+        .unwrap_or_else(|| Duration::seconds(0))
+}
+
+fn set_round_time(times: &mut RoundTimes, skill_name: &str, round: usize, time: Duration) {
+    let rounds = times.entry(skill_name.to_string()).or_default();
+    if rounds.len() <= round {
+        rounds.resize(round + 1, Duration::seconds(0));
+    }
+    rounds[round] = time;
+}
+
+/// Practice sessions. If one exists, it is active. Durations are saved in
+/// milliseconds, so a session can be resumed where it was left off.
+#[serde_with::serde_as]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct PracticeSession {
     // TODO these could be references to the state on SchedulePlanner
     // but the lifetimes got annoying and I gave up and there is some
@@ -87,31 +323,406 @@ pub struct PracticeSession {
     // probably be Arc anyhow
     pub schedule: Vec<Arc<PracticeSkill>>,
     pub current_skill: Arc<PracticeSkill>,
+    // the current skill's position in the schedule, which can list a skill
+    // more than once when practice is interleaved
+    current_idx: usize,
     pub current_exercise: Option<Arc<PracticeExercise>>,
+    /// Updated by `tick`.
+    #[serde_as(as = "serde_with::DurationMilliSeconds<i64>")]
     pub time_left: Duration,
+    /// How far past its time the current skill has run in overtime. Updated
+    /// by `tick`.
+    #[serde_as(as = "serde_with::DurationMilliSeconds<i64>")]
+    pub time_over: Duration,
     pub start_time: DateTime<Utc>,
     pub skill_start_time: DateTime<Utc>,
+    /// Journal notes taken during this session, keyed by skill name.
+    pub notes: BTreeMap<String, String>,
+    /// How long each skill is practiced for, in each round.
+    #[serde_as(as = "serde_with::DurationMilliSeconds<i64>")]
+    pub skill_practice_time: Duration,
+    /// Positions in the schedule of the skills skipped before their time ran
+    /// out, so each round of an interleaved skill is skipped on its own. A
+    /// skill that is gone back to is no longer skipped, unless it's skipped
+    /// again.
+    pub skipped: BTreeSet<usize>,
+    /// Whether the timer counts past zero instead of completing the skill.
+    pub overtime: bool,
+    // practice time of the warm-up and cool-down, keyed by skill name
+    #[serde_as(as = "BTreeMap<_, serde_with::DurationMilliSeconds<i64>>")]
+    segment_times: BTreeMap<String, Duration>,
+    /// How often to rest between skills, if at all.
+    pub rest_breaks: Option<RestBreaks>,
+    // skills practiced since the last rest break
+    skills_since_rest: usize,
+    // how long the current rest break lasts
+    #[serde_as(as = "serde_with::DurationMilliSeconds<i64>")]
+    rest_time: Duration,
+    state: SessionState,
+    // cumulative time spent paused since the current skill started
+    #[serde_as(as = "serde_with::DurationMilliSeconds<i64>")]
+    pause_time_elapsed: Duration,
+    pause_time_started: Option<DateTime<Utc>>,
+    // time practiced on each skill before its current visit, keyed by name
+    // and listed by round
+    #[serde_as(as = "BTreeMap<_, Vec<serde_with::DurationMilliSeconds<i64>>>")]
+    time_practiced: RoundTimes,
+    // how much of the current skill's time was used up before this visit
+    #[serde_as(as = "serde_with::DurationMilliSeconds<i64>")]
+    visit_offset: Duration,
+    // time added to each skill's practice time, keyed by name and listed by
+    // round
+    #[serde_as(as = "BTreeMap<_, Vec<serde_with::DurationMilliSeconds<i64>>>")]
+    extra_time: RoundTimes,
 }
 
 impl PracticeSession {
-    pub fn new(schedule: Vec<Arc<PracticeSkill>>, current_time: DateTime<Utc>) -> Self {
+    /// Skills in `segment_times` are a warm-up or cool-down, practiced for
+    /// their own time instead of `skill_practice_time`. The schedule can't be
+    /// empty.
+    pub fn new(
+        schedule: Vec<Arc<PracticeSkill>>,
+        skill_practice_time: Duration,
+        segment_times: BTreeMap<String, Duration>,
+        current_time: DateTime<Utc>,
+    ) -> Result<Self, SchedulerError> {
         let schedule: Vec<Arc<PracticeSkill>> = schedule.iter().map(|c| c.clone()).collect();
-        let current_skill = &schedule[0];
-        PracticeSession {
+        let current_skill = schedule.first().ok_or(SchedulerError::MissingSkills())?;
+        let time_left = segment_times
+            .get(&current_skill.skill_name)
+            .cloned()
+            .unwrap_or(skill_practice_time);
+        Ok(PracticeSession {
             schedule: schedule.to_owned(),
             current_skill: Arc::clone(current_skill),
-            time_left: Duration::seconds(0),
+            current_idx: 0,
+            time_left,
+            time_over: Duration::seconds(0),
             // TODO maybe make an Option type
             start_time: current_time,
             skill_start_time: current_time,
             current_exercise: None,
+            notes: BTreeMap::new(),
+            skill_practice_time,
+            skipped: BTreeSet::new(),
+            overtime: false,
+            segment_times,
+            rest_breaks: None,
+            skills_since_rest: 0,
+            rest_time: Duration::seconds(0),
+            state: SessionState::NotStarted,
+            pause_time_elapsed: Duration::seconds(0),
+            pause_time_started: None,
+            time_practiced: RoundTimes::new(),
+            visit_offset: Duration::seconds(0),
+            extra_time: RoundTimes::new(),
+        })
+    }
+
+    pub fn state(&self) -> SessionState {
+        self.state
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state == SessionState::Paused
+    }
+
+    /// Starts the timer on the first skill.
+    pub fn start(&mut self, current_time: DateTime<Utc>) -> Result<()> {
+        if self.state != SessionState::NotStarted {
+            return Err(anyhow::anyhow!(
+                "Can't start a practice session that is {:?}",
+                self.state
+            ));
+        }
+
+        self.start_time = current_time;
+        self.restart_skill_timer(current_time);
+        Ok(())
+    }
+
+    pub fn pause(&mut self, current_time: DateTime<Utc>) -> Result<()> {
+        if self.state != SessionState::Running {
+            return Err(anyhow::anyhow!(
+                "Can't pause a practice session that is {:?}",
+                self.state
+            ));
+        }
+
+        self.state = SessionState::Paused;
+        self.pause_time_started = Some(current_time);
+        Ok(())
+    }
+
+    pub fn resume(&mut self, current_time: DateTime<Utc>) -> Result<()> {
+        if self.state != SessionState::Paused {
+            return Err(anyhow::anyhow!(
+                "Can't resume a practice session that is {:?}",
+                self.state
+            ));
+        }
+
+        if let Some(started) = self.pause_time_started.take() {
+            self.pause_time_elapsed = self.pause_time_elapsed + (current_time - started);
+        }
+        self.state = SessionState::Running;
+        self.tick(current_time);
+        Ok(())
+    }
+
+    /// Updates the time left on the current skill, moving to `SkillComplete`
+    /// once it runs out, or counting `time_over` in overtime. Time spent
+    /// paused doesn't count. During a rest break, updates the time left on
+    /// the break instead.
+    pub fn tick(&mut self, current_time: DateTime<Utc>) -> SessionState {
+        if self.state == SessionState::Resting {
+            self.tick_rest(current_time);
+        } else if self.state == SessionState::Running {
+            let time_elapsed = self.visit_offset + self.visit_time(current_time);
+            let skill_time = self.skill_time();
+            if time_elapsed < skill_time {
+                self.time_left = skill_time - time_elapsed;
+                self.time_over = Duration::seconds(0);
+            } else if self.overtime {
+                self.time_left = Duration::seconds(0);
+                self.time_over = time_elapsed - skill_time;
+            } else {
+                self.time_left = Duration::seconds(0);
+                self.time_over = Duration::seconds(0);
+                self.state = SessionState::SkillComplete;
+            }
+        }
+
+        self.state
+    }
+
+    /// How long the current skill is to be practiced this round, including
+    /// any time added to it.
+    pub fn skill_time(&self) -> Duration {
+        let skill_name = &self.current_skill.skill_name;
+        let practice_time = self
+            .segment_times
+            .get(skill_name)
+            .cloned()
+            .unwrap_or(self.skill_practice_time);
+        practice_time + round_time(&self.extra_time, skill_name, self.current_round())
+    }
+
+    /// Whether a skill is the session's warm-up or cool-down.
+    pub fn is_segment(&self, skill_name: &str) -> bool {
+        self.segment_times.contains_key(skill_name)
+    }
+
+    /// The skills practiced in this session, without the warm-up and
+    /// cool-down. Each is listed once, in the order first practiced.
+    pub fn scheduled_skills(&self) -> Vec<Arc<PracticeSkill>> {
+        let mut seen = BTreeSet::new();
+        self.schedule
+            .iter()
+            .filter(|s| !self.is_segment(&s.skill_name) && seen.insert(s.skill_name.clone()))
+            .cloned()
+            .collect()
+    }
+
+    /// Adds time to the current skill.
+    pub fn extend(&mut self, extra: Duration, current_time: DateTime<Utc>) -> Result<()> {
+        if !matches!(self.state, SessionState::Running | SessionState::Paused) {
+            return Err(anyhow::anyhow!(
+                "Can't add time to a practice session that is {:?}",
+                self.state
+            ));
+        }
+
+        let skill_name = self.current_skill.skill_name.clone();
+        let round = self.current_round();
+        let added = round_time(&self.extra_time, &skill_name, round) + extra;
+        set_round_time(&mut self.extra_time, &skill_name, round, added);
+        self.tick(current_time);
+        Ok(())
+    }
+
+    /// How long the current skill has been practiced since it was last
+    /// started, not counting pauses.
+    fn visit_time(&self, current_time: DateTime<Utc>) -> Duration {
+        let until = self.pause_time_started.unwrap_or(current_time);
+        until - self.skill_start_time - self.pause_time_elapsed
+    }
+
+    /// How long a skill has been practiced in this session, over all of its
+    /// visits and rounds. Outside of overtime, a visit counts no longer than
+    /// the skill's time, even if the session is advanced late.
+    pub fn time_practiced(&self, skill_name: &str, current_time: DateTime<Utc>) -> Duration {
+        let mut practiced = self
+            .time_practiced
+            .get(skill_name)
+            .map(|rounds| rounds.iter().fold(Duration::seconds(0), |sum, t| sum + *t))
+            .unwrap_or_else(|| Duration::seconds(0));
+        if self.current_skill.skill_name == skill_name {
+            practiced = practiced + self.current_visit_practiced(current_time);
+        }
+
+        practiced
+    }
+
+    /// How long the current visit to the current skill counts as practice.
+    fn current_visit_practiced(&self, current_time: DateTime<Utc>) -> Duration {
+        // the current skill hasn't started while resting before it
+        if self.state == SessionState::NotStarted || self.is_resting() {
+            return Duration::seconds(0);
+        }
+
+        let visit = self.visit_time(current_time);
+        match self.overtime {
+            true => visit,
+            false => visit.min(self.skill_time() - self.visit_offset),
+        }
+    }
+
+    /// Which round of the session the current skill is in, counting from 0.
+    pub fn current_round(&self) -> usize {
+        self.round_of(self.current_idx)
+    }
+
+    /// Which round the skill at `idx` of the schedule is practiced in,
+    /// counting from 0. Only interleaved sessions have more than one.
+    pub fn round_of(&self, idx: usize) -> usize {
+        let skill_name = &self.schedule[idx].skill_name;
+        self.schedule[..idx]
+            .iter()
+            .filter(|s| &s.skill_name == skill_name)
+            .count()
+    }
+
+    /// Starts the current skill's timer where it was left off this round, or
+    /// from the full practice time if its time already ran out.
+    fn restart_skill_timer(&mut self, current_time: DateTime<Utc>) {
+        let practiced = round_time(
+            &self.time_practiced,
+            &self.current_skill.skill_name,
+            self.current_round(),
+        );
+        let skill_time = self.skill_time();
+        self.visit_offset = if practiced < skill_time {
+            practiced
+        } else {
+            Duration::seconds(0)
+        };
+        self.skill_start_time = current_time;
+        self.time_left = skill_time - self.visit_offset;
+        self.time_over = Duration::seconds(0);
+        self.pause_time_elapsed = Duration::seconds(0);
+        self.pause_time_started = None;
+        self.state = SessionState::Running;
+    }
+
+    /// Marks the current skill as skipped, unless its time already ran out
+    /// in overtime. The planner's `skip_skill` also moves on to the next one.
+    pub fn skip(&mut self) {
+        if self.time_left > Duration::seconds(0) {
+            self.skipped.insert(self.current_idx);
         }
     }
 
+    /// Whether the skill at `idx` of the schedule was skipped.
+    pub fn is_skipped(&self, idx: usize) -> bool {
+        self.skipped.contains(&idx)
+    }
+
+    /// Names of the skills skipped in every round they were practiced in,
+    /// leaving out the warm-up and cool-down.
+    pub fn skipped_skills(&self) -> BTreeSet<String> {
+        let mut skipped = BTreeSet::new();
+        let mut practiced = BTreeSet::new();
+        for (idx, skill) in self.schedule.iter().enumerate() {
+            if self.is_skipped(idx) {
+                skipped.insert(skill.skill_name.clone());
+            } else {
+                practiced.insert(skill.skill_name.clone());
+            }
+        }
+
+        skipped
+            .difference(&practiced)
+            .filter(|name| !self.is_segment(name))
+            .cloned()
+            .collect()
+    }
+
+    /// Goes back to the previous skill, resuming its remaining time.
+    pub fn go_back(&mut self, current_time: DateTime<Utc>) -> Result<()> {
+        let current_idx = self.get_current_skill_idx();
+        if current_idx == 0 {
+            return Err(anyhow::anyhow!("There is no previous skill to go back to"));
+        }
+
+        self.set_current_skill_idx(current_idx - 1, current_time)
+    }
+
+    /// The positions of the skills that can still be reordered, which are
+    /// those after the current skill apart from the cool-down.
+    pub fn movable_skills(&self) -> Range<usize> {
+        let start = self.get_current_skill_idx() + 1;
+        let mut end = self.schedule.len();
+        if end > start && self.is_segment(&self.schedule[end - 1].skill_name) {
+            end -= 1;
+        }
+
+        start..end
+    }
+
+    /// Moves a skill that hasn't been reached yet to another position after
+    /// the current skill. The warm-up and cool-down stay where they are.
+    pub fn move_remaining_skill(&mut self, from: usize, to: usize) -> Result<()> {
+        let movable = self.movable_skills();
+        if !movable.contains(&from) || !movable.contains(&to) {
+            return Err(anyhow::anyhow!(
+                "Only upcoming skills can be moved, apart from the cool-down"
+            ));
+        }
+
+        let skill = self.schedule.remove(from);
+        self.schedule.insert(to, skill);
+        // skips stay with the skills that were skipped
+        let mut skipped = (0..self.schedule.len())
+            .map(|idx| self.is_skipped(idx))
+            .collect::<Vec<_>>();
+        let moved = skipped.remove(from);
+        skipped.insert(to, moved);
+        self.skipped = (0..skipped.len()).filter(|idx| skipped[*idx]).collect();
+        Ok(())
+    }
+
+    fn finish(&mut self) {
+        self.time_left = Duration::seconds(0);
+        self.time_over = Duration::seconds(0);
+        self.pause_time_started = None;
+        self.state = SessionState::Finished;
+    }
+
+    pub fn get_note(&self, skill: &PracticeSkill) -> Option<&String> {
+        self.notes.get(&skill.skill_name)
+    }
+
+    /// Sets the journal note for a skill in this session. An empty note
+    /// removes it.
+    pub fn set_note(&mut self, skill: &PracticeSkill, note: &str) {
+        if note.trim().is_empty() {
+            self.notes.remove(&skill.skill_name);
+            return;
+        }
+
+        self.notes
+            .insert(skill.skill_name.clone(), note.to_string());
+    }
+
     pub fn next_exercise(&mut self) {
+        if self.current_skill.exercises.is_empty() {
+            self.current_exercise = None;
+            return;
+        }
+
         // if current exercise is none, use the first exercise
         if self.current_exercise.is_none() {
-            // TODO: what if there are no exercises?
             self.current_exercise = Some(self.current_skill.exercises[0].clone());
             return;
         }
@@ -133,9 +744,13 @@ impl PracticeSession {
     }
 
     pub fn previous_exercise(&mut self) {
+        if self.current_skill.exercises.is_empty() {
+            self.current_exercise = None;
+            return;
+        }
+
         // if current exercise is none, use the first exercise
         if self.current_exercise.is_none() {
-            // TODO: what if there are no exercises?
             self.current_exercise = Some(self.current_skill.exercises[0].clone());
             return;
         }
@@ -155,22 +770,22 @@ impl PracticeSession {
             Some(self.current_skill.exercises[current_exercise_idx - 1].clone());
     }
 
-    pub fn set_time_left(&mut self, time_left: Duration) {
-        self.time_left = time_left;
-    }
-
     pub fn get_current_skill_idx(&self) -> usize {
-        self.schedule
-            .iter()
-            .position(|r| *r == self.current_skill)
-            .expect("expected current skill to always be present in schedule")
+        self.current_idx
     }
 
     pub fn set_current_skill_idx(&mut self, idx: usize, current_time: DateTime<Utc>) -> Result<()> {
-        if idx > self.schedule.len() {
+        if idx >= self.schedule.len() {
             return Err(anyhow::anyhow!("Invalid skill index"));
         }
 
+        // bank the time spent on the skill being left, so it can be resumed
+        let leaving = self.current_skill.skill_name.clone();
+        let round = self.current_round();
+        let practiced = round_time(&self.time_practiced, &leaving, round)
+            + self.current_visit_practiced(current_time);
+        set_round_time(&mut self.time_practiced, &leaving, round, practiced);
+
         let mut i = 0;
         for skill in &self.schedule {
             if i == idx {
@@ -179,8 +794,10 @@ impl PracticeSession {
             }
             i = i + 1;
         }
+        self.current_idx = idx;
 
-        self.skill_start_time = current_time;
+        self.skipped.remove(&idx);
+        self.restart_skill_timer(current_time);
 
         // select the correct exercise
         self.current_exercise = None;
@@ -219,11 +836,38 @@ impl<'a> SchedulePlanner {
                     .iter()
                     .map(|c| Arc::new(c.clone()))
                     .collect(),
+                archived_skills: vec![],
+                archived_exercises: vec![],
+                overtime: false,
+                warm_up: None,
+                cool_down: None,
+                rest_breaks: None,
+                exclude_practiced_today: false,
+                interleaving: None,
             },
             history: BTreeMap::new(),
             todays_schedule: None,
+            todays_schedule_time: None,
+            todays_pinned: BTreeSet::new(),
+            todays_excluded: BTreeSet::new(),
             practicing: false,
             practice_session: None,
+            session_listeners: vec![],
+        }
+    }
+
+    /// Creates a planner from a previously saved configuration and history.
+    pub fn from_saved(config: PlannerConfiguration, history: PracticeHistory) -> Self {
+        SchedulePlanner {
+            config,
+            history,
+            todays_schedule: None,
+            todays_schedule_time: None,
+            todays_pinned: BTreeSet::new(),
+            todays_excluded: BTreeSet::new(),
+            practicing: false,
+            practice_session: None,
+            session_listeners: vec![],
         }
     }
 
@@ -237,7 +881,14 @@ impl<'a> SchedulePlanner {
         let mut streak_count = 0;
         let mut next_expected_day = current_time.date().sub(Duration::days(1));
         let mut counted_today = false;
+        let mut last_day_seen = None;
         for (key, _value) in self.history.iter().rev() {
+            // several practice sessions on one day only count once
+            if last_day_seen == Some(key.date()) {
+                continue;
+            }
+            last_day_seen = Some(key.date());
+
             // today counts but is not required to be present
             if key.date() == current_time.date() {
                 if !counted_today {
@@ -275,7 +926,7 @@ impl<'a> SchedulePlanner {
         for (key, value) in self.history.iter().rev() {
             // if the history item is within the last n days...
             if key > &n_days_back.unwrap() {
-                for v in value {
+                for v in &value.skills {
                     // insert into the HashSet for that day
                     let day_skills = match historical_skills.contains_key(&key.date()) {
                         true => historical_skills.get_mut(&key.date()).unwrap(),
@@ -309,10 +960,136 @@ impl<'a> SchedulePlanner {
         seen_days.len()
     }
 
+    /// Records practice done away from the app, such as at a lesson, with how
+    /// long each skill was practiced. The entry counts towards the streak and
+    /// the scheduling of later sessions like any other.
+    pub fn log_practice(
+        &mut self,
+        time: DateTime<Utc>,
+        skill_durations: &[(String, Duration)],
+        current_time: DateTime<Utc>,
+    ) -> Result<(), SchedulerError> {
+        if skill_durations.is_empty() {
+            return Err(SchedulerError::InvalidPracticeLog(
+                "at least one skill must be practiced".to_string(),
+            ));
+        }
+        if time > current_time {
+            return Err(SchedulerError::InvalidPracticeLog(
+                "practice can't be logged in the future".to_string(),
+            ));
+        }
+        if self.history.contains_key(&time) {
+            return Err(SchedulerError::InvalidPracticeLog(format!(
+                "a practice session is already recorded at {}",
+                time
+            )));
+        }
+
+        let mut record = PracticeRecord::default();
+        for (skill_name, duration) in skill_durations {
+            let skill = self.get_skill(skill_name)?;
+            if *duration <= Duration::zero() {
+                return Err(SchedulerError::InvalidPracticeLog(format!(
+                    "{} must be practiced for some time",
+                    skill_name
+                )));
+            }
+            if record.durations.contains_key(skill_name) {
+                return Err(SchedulerError::InvalidPracticeLog(format!(
+                    "{} is listed more than once",
+                    skill_name
+                )));
+            }
+            record.durations.insert(skill_name.clone(), *duration);
+            record.skills.push(skill);
+        }
+        self.history.insert(time, record);
+
+        Ok(())
+    }
+
+    /// When to record practice logged for a past day. Midday UTC is still on
+    /// that day in any timezone within 12 hours of UTC, though not in the few
+    /// further east such as UTC+13. Each later entry on that day is recorded a
+    /// second after the last so they don't collide.
+    pub fn past_log_time(&self, date: NaiveDate) -> DateTime<Utc> {
+        let mut time = DateTime::<Utc>::from_utc(date.and_hms(12, 0, 0), Utc);
+        while self.history.contains_key(&time) {
+            time = time + Duration::seconds(1);
+        }
+
+        time
+    }
+
     pub fn reset_history(&mut self) {
         self.history = BTreeMap::new();
     }
 
+    /// Sets the journal note for a skill in a historical practice session.
+    /// An empty note removes it.
+    pub fn set_history_note(
+        &mut self,
+        time: &DateTime<Utc>,
+        skill_name: &str,
+        note: &str,
+    ) -> Result<()> {
+        let record = self
+            .history
+            .get_mut(time)
+            .ok_or_else(|| anyhow::anyhow!("No practice session at {}", time))?;
+        if !record.skills.iter().any(|s| s.skill_name == skill_name) {
+            return Err(anyhow::anyhow!(
+                "{} was not practiced in the session at {}",
+                skill_name,
+                time
+            ));
+        }
+
+        if note.trim().is_empty() {
+            record.notes.remove(skill_name);
+        } else {
+            record
+                .notes
+                .insert(skill_name.to_string(), note.to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Returns every journal note in history, newest first.
+    pub fn journal_notes(&self) -> Vec<JournalNote> {
+        let mut notes = vec![];
+        for (time, record) in self.history.iter().rev() {
+            for (skill_name, note) in record.notes.iter() {
+                notes.push(JournalNote {
+                    time: *time,
+                    skill_name: skill_name.clone(),
+                    note: note.clone(),
+                });
+            }
+        }
+
+        notes
+    }
+
+    /// Full-text search of the journal notes, newest first. Every word of the
+    /// query must appear in either the note or the skill name, ignoring case.
+    pub fn search_notes(&self, query: &str) -> Vec<JournalNote> {
+        let terms = query
+            .split_whitespace()
+            .map(|t| t.to_lowercase())
+            .collect::<Vec<_>>();
+
+        self.journal_notes()
+            .into_iter()
+            .filter(|n| {
+                let haystack = format!("{} {}", n.skill_name, n.note).to_lowercase();
+                terms.iter().all(|t| haystack.contains(t.as_str()))
+            })
+            .collect()
+    }
+
     pub fn delete_skill(&mut self, skill: Arc<PracticeSkill>) -> Result<()> {
         if let Some(pos) = self.config.skills.iter().position(|x| *x == skill) {
             self.config.skills.remove(pos);
@@ -321,32 +1098,421 @@ impl<'a> SchedulePlanner {
         Ok(())
     }
 
+    pub fn get_skill(&self, skill_name: &str) -> Result<Arc<PracticeSkill>, SchedulerError> {
+        self.config
+            .skills
+            .iter()
+            .find(|s| s.skill_name == skill_name)
+            .cloned()
+            .ok_or_else(|| SchedulerError::UnknownSkill(skill_name.to_string()))
+    }
+
+    /// Replaces a skill, wherever it is referenced in the configuration and
+    /// today's schedule, with an updated copy.
+    pub fn replace_skill(
+        &mut self,
+        skill_name: &str,
+        updated: PracticeSkill,
+    ) -> Result<Arc<PracticeSkill>, SchedulerError> {
+        let updated = Arc::new(updated);
+        let pos = self
+            .config
+            .skills
+            .iter()
+            .position(|s| s.skill_name == skill_name)
+            .ok_or_else(|| SchedulerError::UnknownSkill(skill_name.to_string()))?;
+        self.config.skills[pos] = updated.clone();
+
+        if let Some(schedule) = self.todays_schedule.as_mut() {
+            for s in schedule.iter_mut().filter(|s| s.skill_name == skill_name) {
+                *s = updated.clone();
+            }
+        }
+
+        Ok(updated)
+    }
+
+    fn validate_name(name: &str) -> Result<(), SchedulerError> {
+        if name.trim().is_empty() || name.trim() != name {
+            return Err(SchedulerError::InvalidName(name.to_string()));
+        }
+
+        Ok(())
+    }
+
+    fn skill_name_taken(&self, skill_name: &str) -> bool {
+        self.config
+            .skills
+            .iter()
+            .chain(self.config.archived_skills.iter())
+            .any(|s| s.skill_name == skill_name)
+    }
+
+    pub fn add_skill(&mut self, skill_name: &str) -> Result<Arc<PracticeSkill>, SchedulerError> {
+        Self::validate_name(skill_name)?;
+        if self.skill_name_taken(skill_name) {
+            return Err(SchedulerError::SkillExists(skill_name.to_string()));
+        }
+
+        let skill = Arc::new(PracticeSkill {
+            skill_name: skill_name.to_string(),
+            exercises: vec![],
+        });
+        self.config.skills.push(skill.clone());
+
+        Ok(skill)
+    }
+
+    /// Renames a skill. Its history and journal notes are carried over to the
+    /// new name.
+    pub fn rename_skill(&mut self, skill_name: &str, new_name: &str) -> Result<(), SchedulerError> {
+        Self::validate_name(new_name)?;
+        if self.skill_name_taken(new_name) {
+            return Err(SchedulerError::SkillExists(new_name.to_string()));
+        }
+
+        let mut skill = (*self.get_skill(skill_name)?).clone();
+        skill.skill_name = new_name.to_string();
+        let renamed = self.replace_skill(skill_name, skill)?;
+
+        for segment in [&mut self.config.warm_up, &mut self.config.cool_down]
+            .into_iter()
+            .flatten()
+            .filter(|s| s.skill_name == skill_name)
+        {
+            segment.skill_name = new_name.to_string();
+        }
+
+        for names in [&mut self.todays_pinned, &mut self.todays_excluded] {
+            if names.remove(skill_name) {
+                names.insert(new_name.to_string());
+            }
+        }
+
+        for archived in self
+            .config
+            .archived_exercises
+            .iter_mut()
+            .filter(|e| e.skill_name == skill_name)
+        {
+            archived.skill_name = new_name.to_string();
+        }
+
+        for record in self.history.values_mut() {
+            for s in record
+                .skills
+                .iter_mut()
+                .filter(|s| s.skill_name == skill_name)
+            {
+                *s = renamed.clone();
+            }
+            if let Some(note) = record.notes.remove(skill_name) {
+                record.notes.insert(new_name.to_string(), note);
+            }
+            if let Some(duration) = record.durations.remove(skill_name) {
+                record.durations.insert(new_name.to_string(), duration);
+            }
+            if record.skipped.remove(skill_name) {
+                record.skipped.insert(new_name.to_string());
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn add_exercise(
+        &mut self,
+        skill_name: &str,
+        exercise_name: &str,
+        markdown: &str,
+    ) -> Result<(), SchedulerError> {
+        Self::validate_name(exercise_name)?;
+        let mut skill = (*self.get_skill(skill_name)?).clone();
+        if skill
+            .exercises
+            .iter()
+            .any(|e| e.exercise_name == exercise_name)
+        {
+            return Err(SchedulerError::ExerciseExists(exercise_name.to_string()));
+        }
+
+        skill.exercises.push(Arc::new(PracticeExercise {
+            exercise_name: exercise_name.to_string(),
+            exercise_markdown_contents: markdown.to_string(),
+        }));
+        self.replace_skill(skill_name, skill)?;
+
+        Ok(())
+    }
+
+    pub fn get_exercise(
+        &self,
+        skill_name: &str,
+        exercise_name: &str,
+    ) -> Result<Arc<PracticeExercise>, SchedulerError> {
+        self.get_skill(skill_name)?
+            .exercises
+            .iter()
+            .find(|e| e.exercise_name == exercise_name)
+            .cloned()
+            .ok_or_else(|| SchedulerError::UnknownExercise(exercise_name.to_string()))
+    }
+
+    /// Replaces the markdown contents of an exercise.
+    pub fn update_exercise(
+        &mut self,
+        skill_name: &str,
+        exercise_name: &str,
+        markdown: &str,
+    ) -> Result<(), SchedulerError> {
+        let mut skill = (*self.get_skill(skill_name)?).clone();
+        let exercise = skill
+            .exercises
+            .iter_mut()
+            .find(|e| e.exercise_name == exercise_name)
+            .ok_or_else(|| SchedulerError::UnknownExercise(exercise_name.to_string()))?;
+        *exercise = Arc::new(PracticeExercise {
+            exercise_name: exercise_name.to_string(),
+            exercise_markdown_contents: markdown.to_string(),
+        });
+        self.replace_skill(skill_name, skill)?;
+
+        Ok(())
+    }
+
+    pub fn rename_exercise(
+        &mut self,
+        skill_name: &str,
+        exercise_name: &str,
+        new_name: &str,
+    ) -> Result<(), SchedulerError> {
+        Self::validate_name(new_name)?;
+        let mut skill = (*self.get_skill(skill_name)?).clone();
+        if exercise_name != new_name && skill.exercises.iter().any(|e| e.exercise_name == new_name)
+        {
+            return Err(SchedulerError::ExerciseExists(new_name.to_string()));
+        }
+
+        let exercise = skill
+            .exercises
+            .iter_mut()
+            .find(|e| e.exercise_name == exercise_name)
+            .ok_or_else(|| SchedulerError::UnknownExercise(exercise_name.to_string()))?;
+        *exercise = Arc::new(PracticeExercise {
+            exercise_name: new_name.to_string(),
+            exercise_markdown_contents: exercise.exercise_markdown_contents.clone(),
+        });
+        self.replace_skill(skill_name, skill)?;
+
+        Ok(())
+    }
+
+    /// Moves a skill to the trash. It will no longer be scheduled, but its
+    /// history is kept.
+    pub fn archive_skill(&mut self, skill_name: &str) -> Result<(), SchedulerError> {
+        let skill = self.get_skill(skill_name)?;
+        if self.is_segment_skill(skill_name) {
+            return Err(SchedulerError::InvalidSegment(format!(
+                "{} is the warm-up or cool-down, remove it from there first",
+                skill_name
+            )));
+        }
+        self.delete_skill(skill.clone())?;
+        self.config.archived_skills.push(skill);
+
+        // today's schedule will be refilled without the archived skill
+        if let Some(schedule) = &self.todays_schedule {
+            if schedule.iter().any(|s| s.skill_name == skill_name) {
+                self.todays_schedule = None;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn restore_skill(&mut self, skill_name: &str) -> Result<(), SchedulerError> {
+        let pos = self
+            .config
+            .archived_skills
+            .iter()
+            .position(|s| s.skill_name == skill_name)
+            .ok_or_else(|| SchedulerError::UnknownSkill(skill_name.to_string()))?;
+        let skill = self.config.archived_skills.remove(pos);
+        self.config.skills.push(skill);
+
+        Ok(())
+    }
+
+    /// Permanently removes a skill from the trash. History is not modified.
+    pub fn purge_skill(&mut self, skill_name: &str) -> Result<(), SchedulerError> {
+        let pos = self
+            .config
+            .archived_skills
+            .iter()
+            .position(|s| s.skill_name == skill_name)
+            .ok_or_else(|| SchedulerError::UnknownSkill(skill_name.to_string()))?;
+        self.config.archived_skills.remove(pos);
+        self.config
+            .archived_exercises
+            .retain(|e| e.skill_name != skill_name);
+
+        Ok(())
+    }
+
+    /// Moves an exercise of an active skill to the trash.
+    pub fn archive_exercise(
+        &mut self,
+        skill_name: &str,
+        exercise_name: &str,
+    ) -> Result<(), SchedulerError> {
+        let mut skill = (*self.get_skill(skill_name)?).clone();
+        let pos = skill
+            .exercises
+            .iter()
+            .position(|e| e.exercise_name == exercise_name)
+            .ok_or_else(|| SchedulerError::UnknownExercise(exercise_name.to_string()))?;
+        let exercise = skill.exercises.remove(pos);
+        self.replace_skill(skill_name, skill)?;
+        self.config.archived_exercises.push(ArchivedExercise {
+            skill_name: skill_name.to_string(),
+            exercise,
+        });
+
+        Ok(())
+    }
+
+    /// Restores an exercise from the trash to its skill. The skill must not
+    /// itself be in the trash, or have since gained an exercise of the same
+    /// name.
+    pub fn restore_exercise(&mut self, idx: usize) -> Result<(), SchedulerError> {
+        let archived = self
+            .config
+            .archived_exercises
+            .get(idx)
+            .cloned()
+            .ok_or_else(|| SchedulerError::UnknownExercise(format!("#{}", idx)))?;
+        let mut skill = (*self.get_skill(&archived.skill_name)?).clone();
+        let exercise_name = &archived.exercise.exercise_name;
+        if skill
+            .exercises
+            .iter()
+            .any(|e| &e.exercise_name == exercise_name)
+        {
+            return Err(SchedulerError::ExerciseExists(exercise_name.clone()));
+        }
+        skill.exercises.push(archived.exercise);
+        self.replace_skill(&archived.skill_name, skill)?;
+        self.config.archived_exercises.remove(idx);
+
+        Ok(())
+    }
+
+    /// Permanently removes an exercise from the trash.
+    pub fn purge_exercise(&mut self, idx: usize) -> Result<(), SchedulerError> {
+        if idx >= self.config.archived_exercises.len() {
+            return Err(SchedulerError::UnknownExercise(format!("#{}", idx)));
+        }
+        self.config.archived_exercises.remove(idx);
+
+        Ok(())
+    }
+
     pub fn update_todays_schedule(
         &mut self,
         force_update: bool,
         current_time: DateTime<Utc>,
     ) -> Result<(), SchedulerError> {
-        if self.todays_schedule.is_some() && !force_update {
+        let scheduled_today = match self.todays_schedule_time {
+            Some(t) => t.date() == current_time.date(),
+            None => false,
+        };
+        if self.todays_schedule.is_some() && scheduled_today && !force_update {
             // schedule is already set and we didn't force an update
             return Ok(());
         }
 
-        if self.config.skills.len() == 0 {
+        if self.scheduled_skills().next().is_none() {
+            return Err(SchedulerError::MissingSkills());
+        }
+
+        if !scheduled_today {
+            // pins and exclusions only last for the day they were made
+            self.todays_pinned.clear();
+            self.todays_excluded.clear();
+        }
+
+        // pinned skills survive a shuffle, and everything else is picked again
+        let pinned = match &self.todays_schedule {
+            Some(schedule) => schedule
+                .iter()
+                .filter(|s| self.todays_pinned.contains(&s.skill_name))
+                .cloned()
+                .collect::<Vec<_>>(),
+            None => vec![],
+        };
+        let leave_out = pinned.iter().map(|s| s.skill_name.clone()).collect();
+        let picked = self.pick_skills(
+            self.config.skills_per_day.saturating_sub(pinned.len()),
+            &leave_out,
+            current_time,
+        )?;
+        let schedule = self.fill_around_pins(picked);
+        // every skill was excluded for today
+        if schedule.is_empty() {
             return Err(SchedulerError::MissingSkills());
         }
 
+        self.todays_schedule = Some(schedule);
+        self.todays_pinned
+            .retain(|name| pinned.iter().any(|s| &s.skill_name == name));
+        self.todays_schedule_time = Some(current_time);
+        Ok(())
+    }
+
+    /// Picks up to `count` skills to practice, weighted towards those that
+    /// haven't been practiced recently. Skills named in `leave_out` or
+    /// excluded for today aren't picked.
+    pub(crate) fn pick_skills(
+        &self,
+        count: usize,
+        leave_out: &BTreeSet<String>,
+        current_time: DateTime<Utc>,
+    ) -> Result<Vec<Arc<PracticeSkill>>, SchedulerError> {
         let past_history =
             self.get_history_n_days_back(self.config.skill_repeat_days, current_time)?;
         let prob_bandwidth: f64 = 100.0 / self.config.skill_repeat_days as f64;
 
         let mut probabilities: BTreeMap<Arc<PracticeSkill>, u64> = BTreeMap::new();
 
-        for skill in &self.config.skills {
+        let pickable = self
+            .scheduled_skills()
+            .filter(|s| {
+                !leave_out.contains(&s.skill_name) && !self.todays_excluded.contains(&s.skill_name)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        // everything is picked from again once every skill was practiced today
+        let practiced_today = match self.config.exclude_practiced_today {
+            true => self.skills_practiced_today(current_time),
+            false => BTreeSet::new(),
+        };
+        let mut candidates = pickable
+            .iter()
+            .filter(|s| !practiced_today.contains(&s.skill_name))
+            .cloned()
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            candidates = pickable;
+        }
+
+        for skill in candidates.iter() {
             let mut seen = false;
             let mut d = 0;
             for (_day, day_skills) in past_history.iter() {
                 log::debug!("On day: {}", _day);
-                if day_skills.contains(skill) {
+                // skills are matched by name so edits to their exercises
+                // don't reset their history
+                if day_skills.iter().any(|s| s.skill_name == skill.skill_name) {
                     seen = true;
                 }
 
@@ -365,18 +1531,13 @@ impl<'a> SchedulePlanner {
         }
 
         log::debug!("probabilities: {:#?}", probabilities);
-        self.todays_schedule = Some(
-            probabilities
-                .iter()
-                .collect::<Vec<_>>()
-                .choose_multiple_weighted(&mut thread_rng(), self.config.skills_per_day, |item| {
-                    item.1.to_owned() as f64
-                })
-                .unwrap()
-                .map(|item| item.0.to_owned().to_owned())
-                .collect::<Vec<Arc<PracticeSkill>>>(),
-        );
-        return Ok(());
+        Ok(probabilities
+            .iter()
+            .collect::<Vec<_>>()
+            .choose_multiple_weighted(&mut thread_rng(), count, |item| item.1.to_owned() as f64)
+            .unwrap()
+            .map(|item| item.0.to_owned().to_owned())
+            .collect::<Vec<Arc<PracticeSkill>>>())
     }
 
     pub fn advance_practice_session(&mut self, current_time: DateTime<Utc>) -> Result<()> {
@@ -395,9 +1556,25 @@ impl<'a> SchedulePlanner {
             current_skill_idx,
             self.practice_session.as_ref().unwrap().schedule.len() - 1
         );
+        let session = self.practice_session.as_ref().unwrap();
+        let skill_name = session.current_skill.skill_name.clone();
+        let skipped = session.is_skipped(current_skill_idx);
+        self.fire_event(SessionEvent::SkillEnded {
+            time: current_time,
+            skill_name,
+            skill_index: current_skill_idx,
+            skipped,
+        });
         if current_skill_idx == self.practice_session.as_ref().unwrap().schedule.len() - 1 {
             // practice session is complete
-            return self.mark_todays_practice_completed(current_time);
+            self.mark_todays_practice_completed(current_time)?;
+            self.practice_session.as_mut().unwrap().finish();
+            self.fire_event(SessionEvent::SessionCompleted { time: current_time });
+            return Ok(());
+        }
+
+        if self.start_rest_if_due(current_time)? {
+            return Ok(());
         }
 
         // advance to the next skill
@@ -409,9 +1586,93 @@ impl<'a> SchedulePlanner {
             .set_current_skill_idx(current_skill_idx + 1, current_time)?;
         // self.practice_session = mut_practice;
         // self.practice_session.unwrap().skill_start_time = now;
+        self.fire_skill_started(current_time);
+        Ok(())
+    }
+
+    /// Skips the rest of the current skill, which is recorded as skipped in
+    /// history, and moves on to the next one. In overtime, this is also how a
+    /// skill whose time ran out is finished. During a rest break, this ends
+    /// the break instead.
+    pub fn skip_skill(&mut self, current_time: DateTime<Utc>) -> Result<()> {
+        let session = self
+            .practice_session
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("Expected practice session"))?;
+        if session.is_resting() {
+            return self.end_rest(current_time);
+        }
+
+        session.skip();
+        self.advance_practice_session(current_time)
+    }
+
+    /// Adds time to the current skill of the practice session.
+    pub fn extend_skill(&mut self, extra: Duration, current_time: DateTime<Utc>) -> Result<()> {
+        self.practice_session
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("Expected practice session"))?
+            .extend(extra, current_time)
+    }
+
+    /// Goes back to the previous skill, resuming its remaining time.
+    pub fn go_back_skill(&mut self, current_time: DateTime<Utc>) -> Result<()> {
+        let session = self
+            .practice_session
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("Expected practice session"))?;
+        let skill_name = session.current_skill.skill_name.clone();
+        let skill_index = session.get_current_skill_idx();
+        let resting = session.is_resting();
+        session.go_back(current_time)?;
+
+        // going back during a rest break leaves the break, before its skill
+        // ever started
+        if resting {
+            self.fire_event(SessionEvent::RestEnded { time: current_time });
+        } else {
+            self.fire_event(SessionEvent::SkillEnded {
+                time: current_time,
+                skill_name,
+                skill_index,
+                skipped: false,
+            });
+        }
+        self.fire_skill_started(current_time);
         Ok(())
     }
 
+    /// Moves a skill that hasn't been reached yet to another position after
+    /// the current skill.
+    pub fn move_remaining_skill(&mut self, from: usize, to: usize) -> Result<()> {
+        let session = self
+            .practice_session
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("Expected practice session"))?;
+        session.move_remaining_skill(from, to)?;
+        // today's schedule keeps the order the skills are practiced in
+        self.todays_schedule = Some(session.scheduled_skills());
+
+        Ok(())
+    }
+
+    /// Updates the practice session's timer, advancing to the next skill when
+    /// the current one's time runs out. Returns the session's state after
+    /// the update, `Finished` once the last skill is completed.
+    pub fn tick_practice_session(&mut self, current_time: DateTime<Utc>) -> Result<SessionState> {
+        let session = self
+            .practice_session
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("Expected practice session"))?;
+        match session.tick(current_time) {
+            SessionState::SkillComplete => self.advance_practice_session(current_time)?,
+            SessionState::RestComplete => self.end_rest(current_time)?,
+            _ => {}
+        }
+
+        Ok(self.practice_session.as_ref().unwrap().state())
+    }
+
     pub fn start_skill(&mut self, skill: &PracticeSkill) -> Result<()> {
         log::debug!(
             "Starting {} minute practice for skill: {:#?}",
@@ -438,25 +1699,7 @@ impl<'a> SchedulePlanner {
         // self.timeout_job = Some(handle);
 
         log::debug!("Done practicing skill: {:#?}", skill);
-        // self.play_ding_sound();
-
-        Ok(())
-    }
 
-    pub fn play_ding_sound(&self) -> Result<()> {
-        // TODO these should be singletons or maybe the Sink type idk or a separate audio player task
-        // Get a output stream handle to the default physical sound device
-        // let (_stream, stream_handle) = OutputStream::try_default().unwrap();
-        // // Load a sound from a file, using a path relative to Cargo.toml
-        // let file = BufReader::new(File::open("audio/ding.wav").unwrap());
-        // // Decode that sound file into a source
-        // let source = Decoder::new(file).unwrap();
-
-        // stream_handle.play_raw(source.convert_samples())?;
-
-        // need to sleep for the duration of the ding sound
-        // TODO can't sleep in yew app
-        // thread::sleep(std::time::Duration::from_secs(2));
         Ok(())
     }
 
@@ -471,11 +1714,36 @@ impl<'a> SchedulePlanner {
     }
 
     pub fn start_daily_practice(&mut self, current_time: DateTime<Utc>) -> Result<()> {
-        self.practicing = true;
         // ensure today's schedule has been set
         self.update_todays_schedule(false, current_time)?;
-        let schedule = self.todays_schedule.as_mut().unwrap().clone();
-        self.practice_session = Some(PracticeSession::new(schedule, current_time));
+        if self.todays_schedule.as_ref().unwrap().is_empty() {
+            return Err(SchedulerError::MissingSkills().into());
+        }
+        let (schedule, segment_times) =
+            self.session_schedule(self.todays_schedule.clone().unwrap());
+        let mut session = PracticeSession::new(
+            schedule,
+            self.session_practice_time(),
+            segment_times,
+            current_time,
+        )?;
+        self.practicing = true;
+        session.overtime = self.config.overtime;
+        session.rest_breaks = self.config.rest_breaks.clone();
+        session.start(current_time)?;
+        self.practice_session = Some(session);
+        let skills = self
+            .practice_session
+            .as_ref()
+            .unwrap()
+            .schedule
+            .iter()
+            .map(|s| s.skill_name.clone())
+            .collect();
+        self.fire_event(SessionEvent::SessionStarted {
+            time: current_time,
+            skills,
+        });
         // TODO: this clone could lead to bugs if the UI let you edit exercises during
         // a practice session, but the mutability was giving me issues
         // so this was easiest for now
@@ -484,6 +1752,7 @@ impl<'a> SchedulePlanner {
             self.start_skill(skill)?;
         }
         log::debug!("Finished practicing for today!");
+        self.fire_skill_started(current_time);
 
         // record this practice session in history, save to disk
         // self.mark_todays_practice_completed()?;
@@ -500,42 +1769,40 @@ impl<'a> SchedulePlanner {
 
         self.practicing = false;
 
-        // append today's practice to the history
+        // append today's practice to the history, along with any notes taken
+        let skills = self.todays_schedule.as_ref().unwrap().to_vec();
+        let (notes, skipped, durations) = match &self.practice_session {
+            Some(ps) => (
+                ps.notes.clone(),
+                ps.skipped_skills(),
+                skills
+                    .iter()
+                    .map(|s| {
+                        let practiced = ps.time_practiced(&s.skill_name, current_time);
+                        (s.skill_name.clone(), practiced)
+                    })
+                    .collect(),
+            ),
+            None => (BTreeMap::new(), BTreeSet::new(), BTreeMap::new()),
+        };
         self.history.insert(
             current_time,
-            self.todays_schedule.as_ref().unwrap().to_vec(),
+            PracticeRecord {
+                skills,
+                notes,
+                durations,
+                skipped,
+            },
         );
 
         // unset today's practice on Self
         self.todays_schedule = None;
+        self.todays_schedule_time = None;
+        self.todays_pinned.clear();
+        self.todays_excluded.clear();
 
         // TODO can't save to disk on wasm
         // self.save_to_disk()?;
         Ok(())
     }
-
-    pub fn save_to_disk(&self) -> Result<()> {
-        // TODO need to save history and config
-        let encoded: Vec<u8> = bincode::serialize(&self.config).unwrap();
-        let mut file = File::create("./saved_data/history.bin")?;
-        file.write_all(&encoded)?;
-
-        Ok(())
-    }
-
-    pub fn new_from_disk() -> Result<Self> {
-        // TODO need to load history and config
-        let mut f = File::open("./saved_data/history.bin")?;
-        let mut buffer = Vec::new();
-        f.read_to_end(&mut buffer)?;
-        let decoded: PlannerConfiguration = bincode::deserialize(&buffer[..])?;
-
-        Ok(Self {
-            config: decoded,
-            history: BTreeMap::new(),
-            todays_schedule: None,
-            practicing: false,
-            practice_session: None,
-        })
-    }
 }
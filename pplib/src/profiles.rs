@@ -0,0 +1,101 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::SchedulerError;
+
+/// The profile used when none has been chosen. Data saved before profiles
+/// existed belongs to this profile.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Returns the save file for a profile within `data_dir`. The default profile
+/// keeps the original location so data saved before profiles existed is still
+/// found.
+pub fn profile_save_path(data_dir: &Path, profile: &str) -> PathBuf {
+    if profile == DEFAULT_PROFILE {
+        return data_dir.join("history.bin");
+    }
+
+    data_dir.join("profiles").join(profile).join("history.bin")
+}
+
+/// The named practice profiles and which one is in use.
+///
+/// Each profile has its own configuration and history (and therefore its own
+/// streak), which the frontends persist under a per-profile key or path.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct PracticeProfiles {
+    pub active_profile: String,
+    pub profiles: BTreeSet<String>,
+}
+
+impl Default for PracticeProfiles {
+    fn default() -> Self {
+        let mut profiles = BTreeSet::new();
+        profiles.insert(DEFAULT_PROFILE.to_string());
+        PracticeProfiles {
+            active_profile: DEFAULT_PROFILE.to_string(),
+            profiles,
+        }
+    }
+}
+
+impl PracticeProfiles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.active_profile == DEFAULT_PROFILE
+    }
+
+    /// Profile names are used in storage keys and file paths, so only
+    /// alphanumerics, spaces, `-` and `_` are allowed.
+    pub fn validate_name(name: &str) -> Result<(), SchedulerError> {
+        let valid = !name.trim().is_empty()
+            && name.trim() == name
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == ' ' || c == '-' || c == '_');
+        if !valid {
+            return Err(SchedulerError::InvalidProfileName(name.to_string()));
+        }
+
+        Ok(())
+    }
+
+    pub fn add_profile(&mut self, name: &str) -> Result<(), SchedulerError> {
+        Self::validate_name(name)?;
+        if self.profiles.contains(name) {
+            return Err(SchedulerError::ProfileExists(name.to_string()));
+        }
+
+        self.profiles.insert(name.to_string());
+        Ok(())
+    }
+
+    pub fn remove_profile(&mut self, name: &str) -> Result<(), SchedulerError> {
+        if !self.profiles.contains(name) {
+            return Err(SchedulerError::UnknownProfile(name.to_string()));
+        }
+        if name == DEFAULT_PROFILE {
+            return Err(SchedulerError::DefaultProfileRemoval());
+        }
+        if self.active_profile == name {
+            return Err(SchedulerError::ActiveProfileRemoval());
+        }
+
+        self.profiles.remove(name);
+        Ok(())
+    }
+
+    pub fn switch_profile(&mut self, name: &str) -> Result<(), SchedulerError> {
+        if !self.profiles.contains(name) {
+            return Err(SchedulerError::UnknownProfile(name.to_string()));
+        }
+
+        self.active_profile = name.to_string();
+        Ok(())
+    }
+}
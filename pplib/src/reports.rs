@@ -0,0 +1,153 @@
+//! Queries over the practice history, and the reports built from them.
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::Serialize;
+
+use crate::{PracticeRecord, SchedulePlanner};
+
+/// How much a skill was practiced over a report's period.
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct SkillReport {
+    pub skill_name: String,
+    pub minutes: i64,
+    /// The number of practice sessions that included the skill.
+    pub sessions: usize,
+    /// The last day the skill was practiced, in or before the period.
+    pub last_practiced: Option<NaiveDate>,
+}
+
+/// A summary of the practice history between two days, inclusive.
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct PracticeReport {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub days_practiced: usize,
+    pub total_minutes: i64,
+    pub current_streak: usize,
+    /// The longest run of consecutive days practiced within the period.
+    pub longest_streak: usize,
+    /// Skills practiced in the period, most practiced first.
+    pub skills: Vec<SkillReport>,
+    /// Skills that weren't practiced at all in the period.
+    pub neglected_skills: Vec<SkillReport>,
+}
+
+impl PracticeRecord {
+    /// How long a skill was practiced in this session, or `default` if the
+    /// session was recorded before durations were kept.
+    pub fn skill_duration(&self, skill_name: &str, default: Duration) -> Duration {
+        self.durations.get(skill_name).cloned().unwrap_or(default)
+    }
+}
+
+impl SchedulePlanner {
+    /// Practice sessions completed between two days, inclusive.
+    pub fn history_between(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> impl DoubleEndedIterator<Item = (&DateTime<Utc>, &PracticeRecord)> {
+        let from = DateTime::<Utc>::from_utc(start.and_hms(0, 0, 0), Utc);
+        let to = DateTime::<Utc>::from_utc(end.succ().and_hms(0, 0, 0), Utc);
+        self.history.range(from..to)
+    }
+
+    /// When a skill was last practiced, up to and including `end`.
+    pub fn last_practiced(&self, skill_name: &str, end: NaiveDate) -> Option<DateTime<Utc>> {
+        let to = DateTime::<Utc>::from_utc(end.succ().and_hms(0, 0, 0), Utc);
+        self.history
+            .range(..to)
+            .rev()
+            .find(|(_, record)| record.skills.iter().any(|s| s.skill_name == skill_name))
+            .map(|(time, _)| *time)
+    }
+
+    /// The longest run of consecutive days practiced between two days,
+    /// inclusive.
+    pub fn longest_streak(&self, start: NaiveDate, end: NaiveDate) -> usize {
+        let days = self
+            .history_between(start, end)
+            .map(|(time, _)| time.date().naive_utc())
+            .collect::<BTreeSet<_>>();
+
+        let mut longest = 0;
+        let mut current = 0;
+        let mut previous_day: Option<NaiveDate> = None;
+        for day in days {
+            current = match previous_day {
+                Some(previous) if previous.succ() == day => current + 1,
+                _ => 1,
+            };
+            longest = longest.max(current);
+            previous_day = Some(day);
+        }
+
+        longest
+    }
+
+    pub fn report(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        current_time: DateTime<Utc>,
+    ) -> PracticeReport {
+        let default_duration = self.config.skill_practice_time;
+        let mut days = BTreeSet::new();
+        // time practiced and sessions, keyed by skill name. Durations are
+        // only rounded down to minutes once summed.
+        let mut practiced: BTreeMap<String, (Duration, usize)> = BTreeMap::new();
+        let mut total = Duration::zero();
+        for (time, record) in self.history_between(start, end) {
+            days.insert(time.date());
+            for skill in record.skills.iter() {
+                let duration = record.skill_duration(&skill.skill_name, default_duration);
+                let entry = practiced
+                    .entry(skill.skill_name.clone())
+                    .or_insert((Duration::zero(), 0));
+                entry.0 = entry.0 + duration;
+                entry.1 += 1;
+                total = total + duration;
+            }
+        }
+
+        let last_practiced = |skill_name: &str| {
+            self.last_practiced(skill_name, end)
+                .map(|time| time.date().naive_utc())
+        };
+        let mut skills = practiced
+            .into_iter()
+            .map(|(skill_name, (duration, sessions))| SkillReport {
+                last_practiced: last_practiced(&skill_name),
+                skill_name,
+                minutes: duration.num_minutes(),
+                sessions,
+            })
+            .collect::<Vec<_>>();
+        skills.sort_by_key(|s| Reverse(s.minutes));
+
+        let neglected_skills = self
+            .scheduled_skills()
+            .filter(|skill| !skills.iter().any(|s| s.skill_name == skill.skill_name))
+            .map(|skill| SkillReport {
+                skill_name: skill.skill_name.clone(),
+                minutes: 0,
+                sessions: 0,
+                last_practiced: last_practiced(&skill.skill_name),
+            })
+            .collect();
+
+        PracticeReport {
+            start,
+            end,
+            days_practiced: days.len(),
+            total_minutes: total.num_minutes(),
+            current_streak: self.get_streak(current_time),
+            longest_streak: self.longest_streak(start, end),
+            skills,
+            neglected_skills,
+        }
+    }
+}
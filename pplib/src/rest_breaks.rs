@@ -0,0 +1,137 @@
+//! Rest breaks between a practice session's skills, such as 1 minute after
+//! every skill or 5 minutes after every 3 skills.
+//!
+//! Breaks come between the scheduled skills only, never next to the warm-up
+//! or cool-down, and their time isn't counted as practice.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{PracticeSession, SchedulePlanner, SchedulerError, SessionEvent, SessionState};
+
+#[serde_with::serde_as]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct RestBreaks {
+    /// How many skills are practiced between breaks.
+    pub every_skills: usize,
+    /// How long each break lasts.
+    #[serde_as(as = "serde_with::DurationSeconds<i64>")]
+    pub rest_time: Duration,
+}
+
+impl PracticeSession {
+    pub fn is_resting(&self) -> bool {
+        matches!(
+            self.state,
+            SessionState::Resting | SessionState::RestComplete
+        )
+    }
+
+    /// Moves on to the next skill with a rest break first, if one is due
+    /// after the current skill. Returns whether a break started.
+    pub fn start_rest_if_due(&mut self, current_time: DateTime<Utc>) -> Result<bool> {
+        let rest_breaks = match &self.rest_breaks {
+            Some(rest_breaks) => rest_breaks.clone(),
+            None => return Ok(false),
+        };
+        if self.is_segment(&self.current_skill.skill_name) {
+            return Ok(false);
+        }
+
+        self.skills_since_rest += 1;
+        let next_idx = self.get_current_skill_idx() + 1;
+        let next_is_scheduled = self
+            .schedule
+            .get(next_idx)
+            .is_some_and(|next| !self.is_segment(&next.skill_name));
+        if !next_is_scheduled || self.skills_since_rest < rest_breaks.every_skills {
+            return Ok(false);
+        }
+
+        self.skills_since_rest = 0;
+        self.set_current_skill_idx(next_idx, current_time)?;
+        // the next skill's timer starts over once the break is done
+        self.rest_time = rest_breaks.rest_time;
+        self.time_left = rest_breaks.rest_time;
+        self.state = SessionState::Resting;
+        Ok(true)
+    }
+
+    /// Updates the rest countdown, moving to `RestComplete` once it runs out.
+    pub(crate) fn tick_rest(&mut self, current_time: DateTime<Utc>) {
+        let rested = current_time - self.skill_start_time;
+        if rested >= self.rest_time {
+            self.time_left = Duration::seconds(0);
+            self.state = SessionState::RestComplete;
+        } else {
+            self.time_left = self.rest_time - rested;
+        }
+    }
+
+    /// Ends the rest break, starting the current skill's timer.
+    pub fn end_rest(&mut self, current_time: DateTime<Utc>) -> Result<()> {
+        if !self.is_resting() {
+            return Err(anyhow::anyhow!(
+                "Can't end the rest of a practice session that is {:?}",
+                self.state
+            ));
+        }
+
+        self.restart_skill_timer(current_time);
+        Ok(())
+    }
+}
+
+impl SchedulePlanner {
+    /// Sets how often to rest between skills, or turns breaks off with `None`.
+    pub fn set_rest_breaks(
+        &mut self,
+        rest_breaks: Option<RestBreaks>,
+    ) -> Result<(), SchedulerError> {
+        if let Some(rest_breaks) = &rest_breaks {
+            if rest_breaks.every_skills == 0 {
+                return Err(SchedulerError::InvalidRestBreaks(
+                    "there must be at least one skill between breaks".to_string(),
+                ));
+            }
+            if rest_breaks.rest_time <= Duration::seconds(0) {
+                return Err(SchedulerError::InvalidRestBreaks(
+                    "breaks must last for some time".to_string(),
+                ));
+            }
+        }
+
+        self.config.rest_breaks = rest_breaks;
+        Ok(())
+    }
+
+    /// Starts a rest break before the next skill if one is due, returning
+    /// whether it did.
+    pub(crate) fn start_rest_if_due(&mut self, current_time: DateTime<Utc>) -> Result<bool> {
+        let session = self.practice_session.as_mut().unwrap();
+        if !session.start_rest_if_due(current_time)? {
+            return Ok(false);
+        }
+
+        let event = SessionEvent::RestStarted {
+            time: current_time,
+            skill_name: session.current_skill.skill_name.clone(),
+            rest_seconds: session.rest_time.num_seconds(),
+        };
+        self.fire_event(event);
+        Ok(true)
+    }
+
+    /// Ends the rest break, starting the skill after it. The break ends by
+    /// itself in `tick_practice_session` once its time runs out.
+    pub fn end_rest(&mut self, current_time: DateTime<Utc>) -> Result<()> {
+        self.practice_session
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("Expected practice session"))?
+            .end_rest(current_time)?;
+        self.fire_event(SessionEvent::RestEnded { time: current_time });
+        self.fire_skill_started(current_time);
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+//! Saving the practice session underway, so it can be resumed after the app
+//! is reloaded or crashes.
+//!
+//! The frontends save a `SavedSession` as the session moves along and
+//! delete it once the session is over. One that is still around on the next
+//! start belongs to a session that was cut short, which can be resumed or
+//! discarded.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{PracticeSession, SchedulePlanner, SessionState};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SavedSession {
+    /// When the session was last saved. Time after this isn't counted as
+    /// practice when the session is resumed.
+    pub saved_at: DateTime<Utc>,
+    session: PracticeSession,
+}
+
+impl SavedSession {
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// When the saved session was started.
+    pub fn start_time(&self) -> DateTime<Utc> {
+        self.session.start_time
+    }
+
+    /// The skill the saved session was on.
+    pub fn current_skill_name(&self) -> &str {
+        &self.session.current_skill.skill_name
+    }
+}
+
+impl PracticeSession {
+    /// Puts a session restored from `saved_at` on pause at that moment, so
+    /// the time it spent saved isn't counted. A rest break is cut short.
+    fn pause_restored(&mut self, saved_at: DateTime<Utc>) {
+        match self.state {
+            SessionState::Running => {
+                self.state = SessionState::Paused;
+                self.pause_time_started = Some(saved_at);
+            }
+            SessionState::Resting | SessionState::RestComplete => {
+                self.restart_skill_timer(saved_at);
+                self.state = SessionState::Paused;
+                self.pause_time_started = Some(saved_at);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl SchedulePlanner {
+    /// The practice session underway, to save as it moves along. `None`
+    /// when there isn't one.
+    pub fn save_session(&self, current_time: DateTime<Utc>) -> Option<SavedSession> {
+        if !self.practicing {
+            return None;
+        }
+
+        let mut session = self.practice_session.as_ref()?.clone();
+        session.tick(current_time);
+        Some(SavedSession {
+            saved_at: current_time,
+            session,
+        })
+    }
+
+    /// Picks a saved session back up, paused where it was saved. It replaces
+    /// today's schedule with the session's skills.
+    pub fn restore_session(&mut self, saved: SavedSession) -> Result<()> {
+        if self.practicing {
+            return Err(anyhow::anyhow!(
+                "Can't resume a practice session while practicing"
+            ));
+        }
+
+        let mut session = saved.session;
+        if matches!(
+            session.state(),
+            SessionState::NotStarted | SessionState::Finished
+        ) {
+            return Err(anyhow::anyhow!("The saved practice session isn't underway"));
+        }
+
+        session.pause_restored(saved.saved_at);
+        self.todays_schedule = Some(session.scheduled_skills());
+        self.todays_schedule_time = Some(session.start_time);
+        self.practice_session = Some(session);
+        self.practicing = true;
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+//! Editing today's schedule before practicing it.
+//!
+//! A skill can be pinned so shuffling the schedule keeps it in place,
+//! swapped for another pick, excluded for the rest of the day, or added by
+//! hand. Open slots are refilled with the usual weighted picks, which never
+//! include an excluded skill.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+use crate::{PracticeSkill, SchedulePlanner, SchedulerError};
+
+impl SchedulePlanner {
+    pub fn is_pinned(&self, skill_name: &str) -> bool {
+        self.todays_pinned.contains(skill_name)
+    }
+
+    /// Pins a skill in today's schedule so a shuffle keeps it, or unpins it.
+    pub fn pin_skill(&mut self, skill_name: &str, pinned: bool) -> Result<(), SchedulerError> {
+        self.todays_skill_idx(skill_name)?;
+        if pinned {
+            self.todays_pinned.insert(skill_name.to_string());
+        } else {
+            self.todays_pinned.remove(skill_name);
+        }
+
+        Ok(())
+    }
+
+    /// Replaces a skill in today's schedule with another pick, returning the
+    /// skill now in its place.
+    pub fn swap_skill(
+        &mut self,
+        skill_name: &str,
+        current_time: DateTime<Utc>,
+    ) -> Result<Arc<PracticeSkill>, SchedulerError> {
+        let idx = self.todays_skill_idx(skill_name)?;
+        let replacement = self
+            .pick_skills(1, &self.todays_skill_names(), current_time)?
+            .pop()
+            .ok_or_else(|| {
+                SchedulerError::InvalidScheduleEdit(format!(
+                    "there is no other skill to swap {} for",
+                    skill_name
+                ))
+            })?;
+
+        self.todays_pinned.remove(skill_name);
+        self.todays_schedule.as_mut().unwrap()[idx] = replacement.clone();
+        Ok(replacement)
+    }
+
+    /// Takes a skill out of today's schedule for the rest of the day,
+    /// returning the skill picked in its place. The slot is dropped when
+    /// there is nothing left to pick, but the last skill left can't be.
+    pub fn exclude_skill(
+        &mut self,
+        skill_name: &str,
+        current_time: DateTime<Utc>,
+    ) -> Result<Option<Arc<PracticeSkill>>, SchedulerError> {
+        let idx = self.todays_skill_idx(skill_name)?;
+        let replacement = self
+            .pick_skills(1, &self.todays_skill_names(), current_time)?
+            .pop();
+        if replacement.is_none() && self.todays_skill_names().len() == 1 {
+            return Err(SchedulerError::InvalidScheduleEdit(format!(
+                "{} is the only skill left in today's schedule",
+                skill_name
+            )));
+        }
+
+        self.todays_pinned.remove(skill_name);
+        self.todays_excluded.insert(skill_name.to_string());
+        let schedule = self.todays_schedule.as_mut().unwrap();
+        match &replacement {
+            Some(replacement) => schedule[idx] = replacement.clone(),
+            None => {
+                schedule.remove(idx);
+            }
+        }
+
+        Ok(replacement)
+    }
+
+    /// Adds a skill to the end of today's schedule, pinned so a shuffle
+    /// keeps it. A skill excluded earlier in the day can be added back.
+    pub fn add_skill_today(&mut self, skill_name: &str) -> Result<(), SchedulerError> {
+        if self.practicing {
+            return Err(Self::practicing_edit_error());
+        }
+        let skill = self.get_skill(skill_name)?;
+        if self.is_segment_skill(skill_name) {
+            return Err(SchedulerError::InvalidScheduleEdit(format!(
+                "{} is the warm-up or cool-down",
+                skill_name
+            )));
+        }
+        let schedule = self.todays_schedule.as_mut().ok_or_else(|| {
+            SchedulerError::InvalidScheduleEdit("today's schedule hasn't been picked".to_string())
+        })?;
+        if schedule.iter().any(|s| s.skill_name == skill_name) {
+            return Err(SchedulerError::InvalidScheduleEdit(format!(
+                "{} is already in today's schedule",
+                skill_name
+            )));
+        }
+
+        schedule.push(skill);
+        self.todays_excluded.remove(skill_name);
+        self.todays_pinned.insert(skill_name.to_string());
+        Ok(())
+    }
+
+    /// Lays newly picked skills out around the pinned skills of today's
+    /// schedule, which keep their places.
+    pub(crate) fn fill_around_pins(
+        &self,
+        picked: Vec<Arc<PracticeSkill>>,
+    ) -> Vec<Arc<PracticeSkill>> {
+        let mut picked = picked.into_iter();
+        let mut schedule = vec![];
+        for skill in self.todays_schedule.iter().flatten() {
+            if self.is_pinned(&skill.skill_name) {
+                schedule.push(skill.clone());
+            } else if let Some(next) = picked.next() {
+                schedule.push(next);
+            }
+        }
+        schedule.extend(picked);
+
+        schedule
+    }
+
+    fn todays_skill_names(&self) -> BTreeSet<String> {
+        self.todays_schedule
+            .iter()
+            .flatten()
+            .map(|s| s.skill_name.clone())
+            .collect()
+    }
+
+    /// Where a skill is in today's schedule, as long as it can be edited.
+    fn todays_skill_idx(&self, skill_name: &str) -> Result<usize, SchedulerError> {
+        if self.practicing {
+            return Err(Self::practicing_edit_error());
+        }
+        self.get_skill(skill_name)?;
+
+        self.todays_schedule
+            .as_ref()
+            .and_then(|schedule| schedule.iter().position(|s| s.skill_name == skill_name))
+            .ok_or_else(|| {
+                SchedulerError::InvalidScheduleEdit(format!(
+                    "{} isn't in today's schedule",
+                    skill_name
+                ))
+            })
+    }
+
+    fn practicing_edit_error() -> SchedulerError {
+        SchedulerError::InvalidScheduleEdit(
+            "today's schedule can't be changed while practicing".to_string(),
+        )
+    }
+}
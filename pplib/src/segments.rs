@@ -0,0 +1,142 @@
+//! Warm-up and cool-down segments, which wrap every practice session's
+//! scheduled skills.
+//!
+//! A segment practices the exercises of one of the configured skills for its
+//! own amount of time. Skills used by a segment are left out of scheduling,
+//! so they don't count towards the repeat-days weighting.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+
+use crate::{PracticeSkill, SchedulePlanner, SchedulerError};
+
+#[serde_with::serde_as]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct PracticeSegment {
+    /// The skill whose exercises are practiced in the segment.
+    pub skill_name: String,
+    /// How long the segment is practiced.
+    #[serde_as(as = "serde_with::DurationSeconds<i64>")]
+    pub practice_time: Duration,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SegmentKind {
+    WarmUp,
+    CoolDown,
+}
+
+impl SegmentKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SegmentKind::WarmUp => "warm-up",
+            SegmentKind::CoolDown => "cool-down",
+        }
+    }
+}
+
+impl SchedulePlanner {
+    pub fn segment(&self, kind: SegmentKind) -> Option<&PracticeSegment> {
+        match kind {
+            SegmentKind::WarmUp => self.config.warm_up.as_ref(),
+            SegmentKind::CoolDown => self.config.cool_down.as_ref(),
+        }
+    }
+
+    /// Sets the warm-up or cool-down, or removes it with `None`.
+    pub fn set_segment(
+        &mut self,
+        kind: SegmentKind,
+        segment: Option<PracticeSegment>,
+    ) -> Result<(), SchedulerError> {
+        if let Some(segment) = &segment {
+            self.get_skill(&segment.skill_name)?;
+            if segment.practice_time <= Duration::seconds(0) {
+                return Err(SchedulerError::InvalidSegment(format!(
+                    "the {} must be practiced for some time",
+                    kind.name()
+                )));
+            }
+
+            let other = match kind {
+                SegmentKind::WarmUp => SegmentKind::CoolDown,
+                SegmentKind::CoolDown => SegmentKind::WarmUp,
+            };
+            if self.segment(other).map(|s| &s.skill_name) == Some(&segment.skill_name) {
+                return Err(SchedulerError::InvalidSegment(format!(
+                    "{} is already the {}",
+                    segment.skill_name,
+                    other.name()
+                )));
+            }
+        }
+
+        match kind {
+            SegmentKind::WarmUp => self.config.warm_up = segment,
+            SegmentKind::CoolDown => self.config.cool_down = segment,
+        }
+
+        // today's schedule will be refilled without the segment's skill
+        if let Some(schedule) = &self.todays_schedule {
+            if schedule
+                .iter()
+                .any(|s| self.is_segment_skill(&s.skill_name))
+            {
+                self.todays_schedule = None;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether a skill is practiced as the warm-up or cool-down.
+    pub fn is_segment_skill(&self, skill_name: &str) -> bool {
+        [SegmentKind::WarmUp, SegmentKind::CoolDown]
+            .iter()
+            .any(|kind| self.segment(*kind).map(|s| s.skill_name.as_str()) == Some(skill_name))
+    }
+
+    /// The skills that can be scheduled, which are those not used by a
+    /// segment.
+    pub fn scheduled_skills(&self) -> impl Iterator<Item = &Arc<PracticeSkill>> {
+        self.config
+            .skills
+            .iter()
+            .filter(move |s| !self.is_segment_skill(&s.skill_name))
+    }
+
+    /// Wraps a schedule in the warm-up and cool-down, repeating it for each
+    /// round if practice is interleaved. Returns the session's skills along
+    /// with the practice time of each segment, keyed by name.
+    pub(crate) fn session_schedule(
+        &self,
+        schedule: Vec<Arc<PracticeSkill>>,
+    ) -> (Vec<Arc<PracticeSkill>>, BTreeMap<String, Duration>) {
+        let mut session_schedule = vec![];
+        let mut segment_times = BTreeMap::new();
+        let mut add_segment = |session_schedule: &mut Vec<_>, kind| {
+            if let Some(segment) = self.segment(kind) {
+                if let Ok(skill) = self.get_skill(&segment.skill_name) {
+                    session_schedule.push(skill);
+                    segment_times.insert(segment.skill_name.clone(), segment.practice_time);
+                }
+            }
+        };
+
+        add_segment(&mut session_schedule, SegmentKind::WarmUp);
+        session_schedule.extend(
+            self.interleave(
+                schedule
+                    .into_iter()
+                    .filter(|s| !self.is_segment_skill(&s.skill_name))
+                    .collect(),
+            ),
+        );
+        add_segment(&mut session_schedule, SegmentKind::CoolDown);
+
+        (session_schedule, segment_times)
+    }
+}
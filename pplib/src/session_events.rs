@@ -0,0 +1,220 @@
+//! Events in a practice session's flow, for anything that reacts to them,
+//! such as sounds, the frontends' displays or hooks run outside the app.
+
+use std::fmt::Debug;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::{PracticeSession, SchedulePlanner};
+
+/// Something that happened in a practice session. Serializes with its name
+/// in an `event` field.
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum SessionEvent {
+    SessionStarted {
+        time: DateTime<Utc>,
+        skills: Vec<String>,
+    },
+    SkillStarted {
+        time: DateTime<Utc>,
+        skill_name: String,
+        /// The skill's position in the session's schedule, from zero.
+        skill_index: usize,
+    },
+    SkillEnded {
+        time: DateTime<Utc>,
+        skill_name: String,
+        skill_index: usize,
+        /// Whether the skill was skipped before its time ran out.
+        skipped: bool,
+    },
+    /// A different exercise is shown, including when a skill starts.
+    ExerciseChanged {
+        time: DateTime<Utc>,
+        skill_name: String,
+        /// Missing when the skill has no exercises.
+        exercise_name: Option<String>,
+    },
+    Paused {
+        time: DateTime<Utc>,
+        skill_name: String,
+    },
+    Resumed {
+        time: DateTime<Utc>,
+        skill_name: String,
+    },
+    RestStarted {
+        time: DateTime<Utc>,
+        /// The skill practiced after the break.
+        skill_name: String,
+        rest_seconds: i64,
+    },
+    RestEnded {
+        time: DateTime<Utc>,
+    },
+    SessionCompleted {
+        time: DateTime<Utc>,
+    },
+}
+
+impl SessionEvent {
+    /// The event's name, as used in its `event` field.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SessionEvent::SessionStarted { .. } => "session_started",
+            SessionEvent::SkillStarted { .. } => "skill_started",
+            SessionEvent::SkillEnded { .. } => "skill_ended",
+            SessionEvent::ExerciseChanged { .. } => "exercise_changed",
+            SessionEvent::Paused { .. } => "paused",
+            SessionEvent::Resumed { .. } => "resumed",
+            SessionEvent::RestStarted { .. } => "rest_started",
+            SessionEvent::RestEnded { .. } => "rest_ended",
+            SessionEvent::SessionCompleted { .. } => "session_completed",
+        }
+    }
+
+    pub fn time(&self) -> DateTime<Utc> {
+        match self {
+            SessionEvent::SessionStarted { time, .. }
+            | SessionEvent::SkillStarted { time, .. }
+            | SessionEvent::SkillEnded { time, .. }
+            | SessionEvent::ExerciseChanged { time, .. }
+            | SessionEvent::Paused { time, .. }
+            | SessionEvent::Resumed { time, .. }
+            | SessionEvent::RestStarted { time, .. }
+            | SessionEvent::RestEnded { time }
+            | SessionEvent::SessionCompleted { time } => *time,
+        }
+    }
+
+    /// The skill the event is about, if any.
+    pub fn skill_name(&self) -> Option<&str> {
+        match self {
+            SessionEvent::SkillStarted { skill_name, .. }
+            | SessionEvent::SkillEnded { skill_name, .. }
+            | SessionEvent::ExerciseChanged { skill_name, .. }
+            | SessionEvent::Paused { skill_name, .. }
+            | SessionEvent::Resumed { skill_name, .. }
+            | SessionEvent::RestStarted { skill_name, .. } => Some(skill_name),
+            SessionEvent::SessionStarted { .. }
+            | SessionEvent::RestEnded { .. }
+            | SessionEvent::SessionCompleted { .. } => None,
+        }
+    }
+}
+
+/// Subscribes to practice session events with
+/// `SchedulePlanner::add_session_listener`.
+pub trait SessionListener: Debug {
+    fn on_event(&mut self, event: &SessionEvent) -> Result<()>;
+}
+
+impl SchedulePlanner {
+    /// Adds a listener told about every event of the practice sessions that
+    /// follow, after the listeners already added.
+    pub fn add_session_listener(&mut self, listener: Box<dyn SessionListener>) {
+        self.session_listeners.push(listener);
+    }
+
+    pub(crate) fn fire_event(&mut self, event: SessionEvent) {
+        for listener in self.session_listeners.iter_mut() {
+            // a failing listener, such as a missing sound device, shouldn't
+            // interrupt practice
+            if let Err(e) = listener.on_event(&event) {
+                log::warn!("Session listener failed on {}: {}", event.name(), e);
+            }
+        }
+    }
+
+    pub(crate) fn fire_skill_started(&mut self, current_time: DateTime<Utc>) {
+        let session = self.practice_session.as_ref().unwrap();
+        let event = SessionEvent::SkillStarted {
+            time: current_time,
+            skill_name: session.current_skill.skill_name.clone(),
+            skill_index: session.get_current_skill_idx(),
+        };
+        self.fire_event(event);
+        self.fire_exercise_changed(current_time);
+    }
+
+    fn fire_exercise_changed(&mut self, current_time: DateTime<Utc>) {
+        let session = self.practice_session.as_ref().unwrap();
+        let event = SessionEvent::ExerciseChanged {
+            time: current_time,
+            skill_name: session.current_skill.skill_name.clone(),
+            exercise_name: session
+                .current_exercise
+                .as_ref()
+                .map(|e| e.exercise_name.clone()),
+        };
+        self.fire_event(event);
+    }
+
+    /// Shows the current skill's next exercise, if there is one.
+    pub fn next_exercise(&mut self, current_time: DateTime<Utc>) -> Result<()> {
+        self.change_exercise(current_time, PracticeSession::next_exercise)
+    }
+
+    /// Shows the current skill's previous exercise, if there is one.
+    pub fn previous_exercise(&mut self, current_time: DateTime<Utc>) -> Result<()> {
+        self.change_exercise(current_time, PracticeSession::previous_exercise)
+    }
+
+    fn change_exercise(
+        &mut self,
+        current_time: DateTime<Utc>,
+        change: fn(&mut PracticeSession),
+    ) -> Result<()> {
+        let session = self
+            .practice_session
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("Expected practice session"))?;
+        let previous = session.current_exercise.clone();
+        change(session);
+        if session.current_exercise != previous {
+            self.fire_exercise_changed(current_time);
+        }
+
+        Ok(())
+    }
+
+    /// Pauses the practice session's timer.
+    pub fn pause_practice_session(&mut self, current_time: DateTime<Utc>) -> Result<()> {
+        self.practice_session
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("Expected practice session"))?
+            .pause(current_time)?;
+        let skill_name = self.current_skill_name()?;
+        self.fire_event(SessionEvent::Paused {
+            time: current_time,
+            skill_name,
+        });
+
+        Ok(())
+    }
+
+    /// Resumes the practice session's timer after a pause.
+    pub fn resume_practice_session(&mut self, current_time: DateTime<Utc>) -> Result<()> {
+        self.practice_session
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("Expected practice session"))?
+            .resume(current_time)?;
+        let skill_name = self.current_skill_name()?;
+        self.fire_event(SessionEvent::Resumed {
+            time: current_time,
+            skill_name,
+        });
+
+        Ok(())
+    }
+
+    fn current_skill_name(&self) -> Result<String> {
+        self.practice_session
+            .as_ref()
+            .map(|session| session.current_skill.skill_name.clone())
+            .ok_or_else(|| anyhow::anyhow!("Expected practice session"))
+    }
+}
@@ -0,0 +1,134 @@
+//! Saving a planner to disk.
+//!
+//! The save file is JSON, so a field added to the configuration or history
+//! with `#[serde(default)]` still loads from a file saved before it existed.
+//! Files from before the save format was versioned held just the
+//! bincode-encoded configuration, and are migrated when loaded.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use bincode::Options;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{PlannerConfiguration, PracticeHistory, PracticeSkill, SchedulePlanner};
+
+/// Bumped whenever the save format changes incompatibly.
+pub const SAVE_FORMAT_VERSION: u32 = 1;
+
+/// The part of a planner that is saved to disk.
+#[derive(Serialize, Deserialize)]
+struct SavedPlanner {
+    format_version: u32,
+    config: PlannerConfiguration,
+    #[serde(default)]
+    history: PracticeHistory,
+    #[serde(default)]
+    todays_schedule: Option<Vec<Arc<PracticeSkill>>>,
+    #[serde(default)]
+    todays_schedule_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    todays_pinned: BTreeSet<String>,
+    #[serde(default)]
+    todays_excluded: BTreeSet<String>,
+}
+
+/// Just enough of a save file to check its version before reading the rest.
+#[derive(Deserialize)]
+struct SaveHeader {
+    format_version: u32,
+}
+
+/// The configuration as saved before the save format was versioned.
+#[serde_with::serde_as]
+#[derive(Deserialize)]
+struct UnversionedConfiguration {
+    #[serde_as(as = "serde_with::DurationSeconds<i64>")]
+    skill_practice_time: Duration,
+    skill_repeat_days: usize,
+    skills_per_day: usize,
+    skills: Vec<Arc<PracticeSkill>>,
+}
+
+impl SavedPlanner {
+    fn from_json(header: SaveHeader, buffer: &[u8]) -> Result<Self> {
+        if header.format_version > SAVE_FORMAT_VERSION {
+            return Err(anyhow::anyhow!(
+                "Save file version {} is newer than this version supports",
+                header.format_version
+            ));
+        }
+
+        Ok(serde_json::from_slice(buffer)?)
+    }
+
+    fn from_unversioned(buffer: &[u8]) -> Result<Self> {
+        let unversioned: UnversionedConfiguration = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .reject_trailing_bytes()
+            .deserialize(buffer)?;
+
+        let mut config = SchedulePlanner::new().config;
+        config.skill_practice_time = unversioned.skill_practice_time;
+        config.skill_repeat_days = unversioned.skill_repeat_days;
+        config.skills_per_day = unversioned.skills_per_day;
+        config.skills = unversioned.skills;
+        Ok(SavedPlanner {
+            format_version: SAVE_FORMAT_VERSION,
+            config,
+            history: PracticeHistory::new(),
+            todays_schedule: None,
+            todays_schedule_time: None,
+            todays_pinned: BTreeSet::new(),
+            todays_excluded: BTreeSet::new(),
+        })
+    }
+}
+
+impl SchedulePlanner {
+    /// Saves the configuration and history to `path`, creating any missing
+    /// parent directories.
+    pub fn save_to_disk(&self, path: &Path) -> Result<()> {
+        let saved = SavedPlanner {
+            format_version: SAVE_FORMAT_VERSION,
+            config: self.config.clone(),
+            history: self.history.clone(),
+            todays_schedule: self.todays_schedule.clone(),
+            todays_schedule_time: self.todays_schedule_time,
+            todays_pinned: self.todays_pinned.clone(),
+            todays_excluded: self.todays_excluded.clone(),
+        };
+        let encoded = serde_json::to_vec(&saved)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, encoded)?;
+
+        Ok(())
+    }
+
+    /// Loads a planner saved by `save_to_disk`, or by a version from before
+    /// the save format was versioned.
+    pub fn new_from_disk(path: &Path) -> Result<Self> {
+        let buffer = fs::read(path)?;
+        let saved = match serde_json::from_slice::<SaveHeader>(&buffer) {
+            Ok(header) => SavedPlanner::from_json(header, &buffer)?,
+            // files from before the save format was versioned aren't JSON
+            Err(e) => SavedPlanner::from_unversioned(&buffer)
+                .map_err(|_| e)
+                .context("Unrecognized save file")?,
+        };
+
+        let mut planner = Self::from_saved(saved.config, saved.history);
+        planner.todays_schedule = saved.todays_schedule;
+        planner.todays_schedule_time = saved.todays_schedule_time;
+        planner.todays_pinned = saved.todays_pinned;
+        planner.todays_excluded = saved.todays_excluded;
+
+        Ok(planner)
+    }
+}
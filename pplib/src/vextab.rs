@@ -0,0 +1,230 @@
+//! Plain text handling of the vextab blocks embedded in exercise markdown.
+//!
+//! The webapp renders these blocks with the vextab javascript library. This
+//! module understands the subset of the `notes` syntax used by exercises well
+//! enough to check it and to draw it as six-line ASCII tablature.
+
+use crate::SchedulerError;
+
+const VEXTAB_OPEN: &str = "<div class=\"vextab-auto\"";
+const VEXTAB_CLOSE: &str = "</div>";
+
+/// String names from the highest (string 1) to the lowest (string 6).
+const STRING_NAMES: [&str; 6] = ["e", "B", "G", "D", "A", "E"];
+
+/// A part of an exercise's markdown contents.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ExerciseBlock {
+    Markdown(String),
+    Vextab(String),
+}
+
+/// Splits exercise markdown into plain markdown and the vextab source of any
+/// `<div class="vextab-auto">` blocks.
+pub fn split_vextab_blocks(markdown: &str) -> Vec<ExerciseBlock> {
+    let mut blocks = vec![];
+    let mut rest = markdown;
+    while let Some(start) = rest.find(VEXTAB_OPEN) {
+        if start > 0 {
+            blocks.push(ExerciseBlock::Markdown(rest[..start].to_string()));
+        }
+
+        let div = &rest[start..];
+        // the vextab source starts after the opening tag
+        let source_start = div.find('>').map(|i| i + 1).unwrap_or(div.len());
+        let source_end = div[source_start..]
+            .find(VEXTAB_CLOSE)
+            .map(|i| source_start + i)
+            .unwrap_or(div.len());
+        blocks.push(ExerciseBlock::Vextab(
+            div[source_start..source_end].to_string(),
+        ));
+
+        rest = &div[(source_end + VEXTAB_CLOSE.len()).min(div.len())..];
+    }
+
+    if !rest.is_empty() {
+        blocks.push(ExerciseBlock::Markdown(rest.to_string()));
+    }
+
+    blocks
+}
+
+/// A vertical slice of a tab stave.
+#[derive(Clone, Debug)]
+enum Column {
+    /// The text to draw on each string, if any.
+    Notes([Option<String>; 6]),
+    Rest,
+    Bar,
+}
+
+fn invalid(line: usize, message: String) -> SchedulerError {
+    SchedulerError::InvalidVextab { line, message }
+}
+
+/// Parses a single note such as `5`, `5h7` or `x`, returning its text.
+fn parse_fret(fret: &str, line: usize) -> Result<String, SchedulerError> {
+    let valid = fret
+        .chars()
+        .any(|c| c.is_ascii_digit() || c == 'x' || c == 'X')
+        && fret
+            .chars()
+            .all(|c| c.is_ascii_digit() || "xXhpbstvV~".contains(c));
+    if !valid {
+        return Err(invalid(line, format!("invalid fret {:?}", fret)));
+    }
+
+    Ok(fret.to_lowercase())
+}
+
+fn parse_string(string: &str, line: usize) -> Result<usize, SchedulerError> {
+    match string.parse::<usize>() {
+        Ok(s) if (1..=6).contains(&s) => Ok(s - 1),
+        _ => Err(invalid(line, format!("invalid string {:?}", string))),
+    }
+}
+
+/// Parses a `frets/string` group such as `1-2-3-4/6` into one column per fret.
+fn parse_note_group(group: &str, line: usize) -> Result<Vec<Column>, SchedulerError> {
+    let (frets, string) = group
+        .rsplit_once('/')
+        .ok_or_else(|| invalid(line, format!("expected fret/string, got {:?}", group)))?;
+    let string = parse_string(string, line)?;
+
+    frets
+        .split('-')
+        .map(|fret| {
+            let mut notes: [Option<String>; 6] = Default::default();
+            notes[string] = Some(parse_fret(fret, line)?);
+            Ok(Column::Notes(notes))
+        })
+        .collect()
+}
+
+/// Parses a chord such as `(5/2.5/3.7/4)` into a single column.
+fn parse_chord(chord: &str, line: usize) -> Result<Column, SchedulerError> {
+    let inner = chord
+        .strip_prefix('(')
+        .and_then(|c| c.strip_suffix(')'))
+        .ok_or_else(|| invalid(line, format!("unterminated chord {:?}", chord)))?;
+
+    let mut notes: [Option<String>; 6] = Default::default();
+    for note in inner.split('.') {
+        let (fret, string) = note
+            .rsplit_once('/')
+            .ok_or_else(|| invalid(line, format!("expected fret/string, got {:?}", note)))?;
+        notes[parse_string(string, line)?] = Some(parse_fret(fret, line)?);
+    }
+
+    Ok(Column::Notes(notes))
+}
+
+fn parse_duration(duration: &str, line: usize) -> Result<(), SchedulerError> {
+    let value = duration.trim_start_matches(':').trim_end_matches('d');
+    match value {
+        "w" | "h" | "q" | "1" | "2" | "4" | "8" | "16" | "32" | "64" => Ok(()),
+        _ => Err(invalid(line, format!("invalid duration {:?}", duration))),
+    }
+}
+
+fn parse_notes(notes: &str, line: usize) -> Result<Vec<Column>, SchedulerError> {
+    let mut columns = vec![];
+    for token in notes.split_whitespace() {
+        if token.starts_with(':') {
+            // durations only affect spacing in the rendered score
+            parse_duration(token, line)?;
+        } else if token == "|" || token.starts_with('=') {
+            columns.push(Column::Bar);
+        } else if token == "##" {
+            columns.push(Column::Rest);
+        } else if token.starts_with('(') {
+            columns.push(parse_chord(token, line)?);
+        } else {
+            columns.extend(parse_note_group(token, line)?);
+        }
+    }
+
+    Ok(columns)
+}
+
+/// Parses vextab source into staves of columns.
+fn parse_vextab(source: &str) -> Result<Vec<Vec<Column>>, SchedulerError> {
+    let mut staves: Vec<Vec<Column>> = vec![];
+    for (idx, line) in source.lines().enumerate() {
+        let line_number = idx + 1;
+        let line = line.trim();
+        if line.starts_with("tabstave") || line.starts_with("stave") {
+            staves.push(vec![]);
+        } else if let Some(notes) = line.strip_prefix("notes") {
+            if staves.is_empty() {
+                return Err(invalid(
+                    line_number,
+                    "notes before any tabstave".to_string(),
+                ));
+            }
+            let columns = parse_notes(notes, line_number)?;
+            staves.last_mut().unwrap().extend(columns);
+        }
+        // options, text and other lines don't affect the tablature
+    }
+
+    Ok(staves)
+}
+
+/// Checks that vextab source can be parsed.
+pub fn validate_vextab(source: &str) -> Result<(), SchedulerError> {
+    parse_vextab(source)?;
+    Ok(())
+}
+
+fn render_stave(columns: &[Column]) -> String {
+    let mut lines: Vec<String> = STRING_NAMES.iter().map(|n| format!("{}|", n)).collect();
+    for column in columns {
+        match column {
+            Column::Bar => {
+                for line in lines.iter_mut() {
+                    line.push('|');
+                }
+            }
+            Column::Rest => {
+                for line in lines.iter_mut() {
+                    line.push_str("---");
+                }
+            }
+            Column::Notes(notes) => {
+                let width = notes
+                    .iter()
+                    .map(|n| n.as_ref().map(|n| n.len()).unwrap_or(0))
+                    .max()
+                    .unwrap_or(0);
+                for (line, note) in lines.iter_mut().zip(notes.iter()) {
+                    let note = note.as_deref().unwrap_or("");
+                    line.push('-');
+                    line.push_str(note);
+                    line.push_str(&"-".repeat(width - note.len() + 1));
+                }
+            }
+        }
+    }
+
+    if !matches!(columns.last(), Some(Column::Bar)) {
+        for line in lines.iter_mut() {
+            line.push('|');
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Draws the tab staves of vextab source as six-line ASCII tablature, one
+/// block per stave.
+pub fn vextab_to_ascii(source: &str) -> Result<String, SchedulerError> {
+    let staves = parse_vextab(source)?;
+    Ok(staves
+        .iter()
+        .filter(|s| !s.is_empty())
+        .map(|s| render_stave(s))
+        .collect::<Vec<_>>()
+        .join("\n\n"))
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use anyhow::Result;
+use chrono::Duration;
+use pplib::{
+    Alert, AlertListener, AlertSink, NullAlertSink, RestBreaks, SchedulePlanner, SessionState,
+};
+
+use common::{at, scheduled_planner, start_time};
+
+/// A planner practicing three skills for 5 minutes each, with its alerts
+/// sent to `sink`.
+fn practicing_planner(sink: &NullAlertSink, rest_breaks: Option<RestBreaks>) -> SchedulePlanner {
+    let mut planner = scheduled_planner(&["Scales", "Chords", "Arpeggios"]);
+    planner.set_rest_breaks(rest_breaks).unwrap();
+    planner.add_session_listener(Box::new(AlertListener::new(Box::new(sink.clone()))));
+    planner.start_daily_practice(start_time()).unwrap();
+    planner
+}
+
+#[test]
+fn alerts_when_the_session_moves_on() {
+    let sink = NullAlertSink::default();
+    let rest_breaks = RestBreaks {
+        every_skills: 2,
+        rest_time: Duration::minutes(1),
+    };
+    let mut planner = practicing_planner(&sink, Some(rest_breaks));
+    // starting the session needs no alert
+    assert!(sink.alerts().is_empty());
+
+    planner.tick_practice_session(at(5, 0)).unwrap();
+    assert_eq!(sink.alerts(), vec![Alert::SkillChanged]);
+
+    planner.tick_practice_session(at(10, 0)).unwrap();
+    assert_eq!(sink.alerts(), vec![Alert::SkillChanged, Alert::RestStarted]);
+
+    // the skill after the break starts when the break runs out
+    planner.tick_practice_session(at(11, 0)).unwrap();
+    assert_eq!(
+        sink.alerts(),
+        vec![Alert::SkillChanged, Alert::RestStarted, Alert::SkillChanged]
+    );
+
+    assert_eq!(
+        planner.tick_practice_session(at(16, 0)).unwrap(),
+        SessionState::Finished
+    );
+    assert_eq!(
+        sink.alerts(),
+        vec![
+            Alert::SkillChanged,
+            Alert::RestStarted,
+            Alert::SkillChanged,
+            Alert::SessionCompleted
+        ]
+    );
+}
+
+#[test]
+fn no_alerts_while_the_same_skill_is_practiced() {
+    let sink = NullAlertSink::default();
+    let mut planner = practicing_planner(&sink, None);
+
+    planner.tick_practice_session(at(1, 0)).unwrap();
+    planner.pause_practice_session(at(2, 0)).unwrap();
+    planner.tick_practice_session(at(3, 0)).unwrap();
+    planner.resume_practice_session(at(4, 0)).unwrap();
+    planner.next_exercise(at(4, 0)).unwrap();
+    planner.previous_exercise(at(4, 0)).unwrap();
+    planner.tick_practice_session(at(5, 0)).unwrap();
+
+    assert_eq!(
+        planner.practice_session.as_ref().unwrap().state(),
+        SessionState::Running
+    );
+    assert!(sink.alerts().is_empty());
+}
+
+/// Fails every alert, like a missing sound device.
+#[derive(Debug)]
+struct FailingAlertSink;
+
+impl AlertSink for FailingAlertSink {
+    fn alert(&mut self, _alert: Alert) -> Result<()> {
+        Err(anyhow::anyhow!("no sound device"))
+    }
+}
+
+#[test]
+fn a_failing_alert_does_not_interrupt_practice() {
+    let sink = NullAlertSink::default();
+    let mut planner = practicing_planner(&sink, None);
+    planner.add_session_listener(Box::new(AlertListener::new(Box::new(FailingAlertSink))));
+
+    planner.tick_practice_session(at(5, 0)).unwrap();
+    planner.tick_practice_session(at(10, 0)).unwrap();
+    assert_eq!(
+        planner.tick_practice_session(at(15, 0)).unwrap(),
+        SessionState::Finished
+    );
+    assert_eq!(
+        sink.alerts(),
+        vec![
+            Alert::SkillChanged,
+            Alert::SkillChanged,
+            Alert::SessionCompleted
+        ]
+    );
+}
@@ -0,0 +1,61 @@
+//! Fixtures shared by the integration tests. Not every test uses all of them.
+#![allow(dead_code)]
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use pplib::{PracticeSkill, SchedulePlanner};
+
+pub fn skill(name: &str) -> Arc<PracticeSkill> {
+    Arc::new(PracticeSkill {
+        skill_name: name.to_string(),
+        exercises: vec![],
+    })
+}
+
+pub fn skills(names: &[&str]) -> Vec<Arc<PracticeSkill>> {
+    names.iter().map(|name| skill(name)).collect()
+}
+
+pub fn skill_names(skills: &[Arc<PracticeSkill>]) -> Vec<String> {
+    skills.iter().map(|s| s.skill_name.clone()).collect()
+}
+
+/// When every test's practice starts.
+pub fn start_time() -> DateTime<Utc> {
+    Utc.ymd(2022, 3, 1).and_hms(18, 0, 0)
+}
+
+pub fn at(minutes: i64, seconds: i64) -> DateTime<Utc> {
+    start_time() + Duration::minutes(minutes) + Duration::seconds(seconds)
+}
+
+/// A planner with just the skills named, practiced for 5 minutes each, and
+/// today's schedule set to all of them in order.
+pub fn scheduled_planner(names: &[&str]) -> SchedulePlanner {
+    let skills = skills(names);
+    let mut planner = SchedulePlanner::new();
+    planner.config.skills = skills.clone();
+    planner.config.skills_per_day = skills.len();
+    planner.config.skill_practice_time = Duration::minutes(5);
+    planner.todays_schedule = Some(skills);
+    planner.todays_schedule_time = Some(start_time());
+    planner
+}
+
+/// A planner practicing Scales, Chords and Arpeggios for 5 minutes each,
+/// started at `start_time`.
+pub fn practicing_planner() -> SchedulePlanner {
+    let mut planner = scheduled_planner(&["Scales", "Chords", "Arpeggios"]);
+    planner.start_daily_practice(start_time()).unwrap();
+    planner
+}
+
+/// The index and name of the practice session's current skill.
+pub fn current_skill(planner: &SchedulePlanner) -> (usize, String) {
+    let ps = planner.practice_session.as_ref().unwrap();
+    (
+        ps.get_current_skill_idx(),
+        ps.current_skill.skill_name.clone(),
+    )
+}
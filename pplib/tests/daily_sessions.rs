@@ -0,0 +1,117 @@
+mod common;
+
+use chrono::{DateTime, Duration, Utc};
+use pplib::{SchedulePlanner, SessionState};
+
+use common::{at, scheduled_planner, skill, skill_names, start_time};
+
+fn log(planner: &mut SchedulePlanner, time: DateTime<Utc>, skill_name: &str) {
+    planner
+        .log_practice(
+            time,
+            &[(skill_name.to_string(), Duration::minutes(5))],
+            time,
+        )
+        .unwrap();
+}
+
+/// A planner that practiced just Scales in a session finished at `at(5, 0)`.
+fn practiced_scales_today() -> SchedulePlanner {
+    let mut planner = scheduled_planner(&["Scales", "Chords", "Arpeggios"]);
+    planner.todays_schedule = Some(vec![skill("Scales")]);
+    planner.start_daily_practice(start_time()).unwrap();
+    assert_eq!(
+        planner.tick_practice_session(at(5, 0)).unwrap(),
+        SessionState::Finished
+    );
+    planner
+}
+
+fn sorted_todays_schedule(planner: &SchedulePlanner) -> Vec<String> {
+    let mut names = skill_names(planner.todays_schedule.as_ref().unwrap());
+    names.sort();
+    names
+}
+
+#[test]
+fn every_session_on_a_day_is_listed() {
+    let mut planner = practiced_scales_today();
+    log(&mut planner, at(30, 0), "Chords");
+    log(&mut planner, at(-24 * 60, 0), "Arpeggios");
+
+    let today = planner
+        .todays_sessions(at(40, 0))
+        .map(|(time, record)| (*time, skill_names(&record.skills)))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        today,
+        vec![
+            (at(5, 0), vec!["Scales".to_string()]),
+            (at(30, 0), vec!["Chords".to_string()]),
+        ]
+    );
+    assert_eq!(
+        planner.skills_practiced_today(at(40, 0)),
+        ["Chords", "Scales"].iter().map(|s| s.to_string()).collect()
+    );
+
+    let by_day = planner.sessions_n_days_back(2, at(40, 0)).unwrap();
+    let sessions_per_day = by_day
+        .iter()
+        .map(|(day, sessions)| (*day, sessions.len()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        sessions_per_day,
+        vec![
+            ((start_time() - Duration::days(1)).date(), 1),
+            (start_time().date(), 2)
+        ]
+    );
+}
+
+#[test]
+fn a_day_with_several_sessions_counts_once_towards_the_streak() {
+    let mut planner = practiced_scales_today();
+    log(&mut planner, at(30, 0), "Chords");
+    let yesterday = start_time() - Duration::days(1);
+    log(&mut planner, yesterday, "Arpeggios");
+    log(&mut planner, yesterday + Duration::hours(1), "Scales");
+    log(&mut planner, yesterday - Duration::days(1), "Chords");
+
+    assert_eq!(planner.get_streak(at(40, 0)), 3);
+    // today's two sessions count once more tomorrow
+    assert_eq!(planner.get_streak(at(24 * 60, 0)), 3);
+    assert_eq!(planner.days_of_history(), 3);
+}
+
+#[test]
+fn a_later_session_leaves_out_skills_practiced_earlier_that_day() {
+    let mut planner = practiced_scales_today();
+    planner.config.exclude_practiced_today = true;
+
+    for _ in 0..10 {
+        planner.update_todays_schedule(true, at(10, 0)).unwrap();
+        assert_eq!(
+            sorted_todays_schedule(&planner),
+            vec!["Arpeggios", "Chords"]
+        );
+    }
+
+    // Scales is picked again tomorrow
+    let tomorrow = at(24 * 60, 0);
+    planner.update_todays_schedule(true, tomorrow).unwrap();
+    assert!(planner.skills_practiced_today(tomorrow).is_empty());
+    assert_eq!(planner.todays_schedule.as_ref().unwrap().len(), 3);
+}
+
+#[test]
+fn every_skill_is_picked_from_once_all_were_practiced_today() {
+    let mut planner = practiced_scales_today();
+    planner.config.exclude_practiced_today = true;
+    log(&mut planner, at(10, 0), "Chords");
+    log(&mut planner, at(11, 0), "Arpeggios");
+    planner.config.skills_per_day = 2;
+
+    planner.update_todays_schedule(true, at(20, 0)).unwrap();
+    assert_eq!(planner.todays_schedule.as_ref().unwrap().len(), 2);
+}
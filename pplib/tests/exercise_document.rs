@@ -0,0 +1,68 @@
+use pplib::{validate_exercise_markdown, ExerciseDocument, SchedulerError};
+
+fn front_matter_error(text: &str) -> String {
+    match ExerciseDocument::parse(text) {
+        Err(SchedulerError::InvalidFrontMatter(message)) => message,
+        other => panic!("{:?} gave {:?}", text, other),
+    }
+}
+
+#[test]
+fn a_document_parses_back_to_the_same_exercise() {
+    let document = ExerciseDocument {
+        exercise_name: "Etude".to_string(),
+        markdown: "Play slowly.\n\n<div class=\"vextab-auto\">tabstave\nnotes 1-3/6\n</div>\n"
+            .to_string(),
+    };
+
+    assert_eq!(
+        ExerciseDocument::parse(&document.to_text()).unwrap(),
+        document
+    );
+}
+
+#[test]
+fn the_front_matter_must_be_complete() {
+    assert!(front_matter_error("Play slowly.\n").contains("must start with"));
+    assert!(front_matter_error("").contains("must start with"));
+    assert!(front_matter_error("---\nname: Etude\n").contains("closing"));
+    assert!(front_matter_error("---\n---\n\nPlay slowly.\n").contains("name"));
+    assert!(front_matter_error("---\nname Etude\n---\n").contains("key: value"));
+}
+
+#[test]
+fn unknown_front_matter_keys_are_refused() {
+    let message = front_matter_error("---\nname: Etude\ntempo: 60\n---\n\nPlay slowly.\n");
+    assert!(message.contains("\"tempo\""), "{}", message);
+}
+
+#[test]
+fn vextab_errors_count_lines_from_the_top_of_the_document() {
+    let text = [
+        "---",
+        "name: Etude",
+        "---",
+        "",
+        "Play slowly.",
+        "<div class=\"vextab-auto\">tabstave",
+        "notes 1/9",
+        "</div>",
+    ]
+    .join("\n");
+
+    assert!(matches!(
+        ExerciseDocument::parse(&text),
+        Err(SchedulerError::InvalidVextab { line: 7, .. })
+    ));
+}
+
+#[test]
+fn vextab_errors_count_lines_from_the_top_of_the_markdown() {
+    let markdown = "Intro\n\n<div class=\"vextab-auto\">tabstave\nnotes 1/6\n</div>\n\
+                    Then\n<div class=\"vextab-auto\">tabstave\nnotes 1/6 q/6\n</div>\n";
+
+    assert!(matches!(
+        validate_exercise_markdown(markdown),
+        Err(SchedulerError::InvalidVextab { line: 8, .. })
+    ));
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use chrono::Duration;
+use pplib::{SchedulePlanner, SchedulerError, EXPORT_FORMAT_VERSION};
+
+use common::{skill_names, skills, start_time};
+
+/// A planner with its own skills and a session logged on each of `days`
+/// before `start_time`.
+fn planner_with_history(days: &[i64]) -> SchedulePlanner {
+    let mut planner = SchedulePlanner::new();
+    planner.config.skills = skills(&["Scales", "Chords"]);
+    planner.config.skill_practice_time = Duration::minutes(7);
+    for day in days {
+        planner
+            .log_practice(
+                start_time() - Duration::days(*day),
+                &[("Scales".to_string(), Duration::minutes(7))],
+                start_time(),
+            )
+            .unwrap();
+    }
+    planner
+}
+
+#[test]
+fn an_export_imports_into_an_empty_planner() {
+    let exported = planner_with_history(&[1, 2]);
+    let json = exported.export_json(start_time()).unwrap();
+
+    let mut planner = SchedulePlanner::new();
+    planner.update_todays_schedule(false, start_time()).unwrap();
+    assert_eq!(planner.import_json(&json).unwrap(), 2);
+
+    assert_eq!(planner.config, exported.config);
+    assert_eq!(planner.history, exported.history);
+    // today's schedule was picked from the skills that were replaced
+    assert!(planner.todays_schedule.is_none());
+}
+
+#[test]
+fn importing_overlapping_history_counts_only_new_sessions() {
+    let exported = planner_with_history(&[1, 2, 3]);
+    let json = exported.export_json(start_time()).unwrap();
+
+    let mut planner = planner_with_history(&[2, 3, 4]);
+    assert_eq!(planner.import_json(&json).unwrap(), 1);
+    assert_eq!(planner.history.len(), 4);
+
+    // importing the same file again adds nothing
+    assert_eq!(planner.import_json(&json).unwrap(), 0);
+    assert_eq!(planner.history.len(), 4);
+}
+
+#[test]
+fn refuses_an_export_from_a_newer_version() {
+    let exported = planner_with_history(&[1]);
+    let mut export: serde_json::Value =
+        serde_json::from_str(&exported.export_json(start_time()).unwrap()).unwrap();
+    export["format_version"] = (EXPORT_FORMAT_VERSION + 1).into();
+
+    let mut planner = SchedulePlanner::new();
+    let before = planner.config.clone();
+    assert!(matches!(
+        planner.import_json(&export.to_string()),
+        Err(SchedulerError::UnsupportedExportVersion(v)) if v == EXPORT_FORMAT_VERSION + 1
+    ));
+    assert_eq!(planner.config, before);
+    assert!(planner.history.is_empty());
+}
+
+#[test]
+fn refuses_a_file_that_is_not_an_export() {
+    let mut planner = SchedulePlanner::new();
+
+    // a save file has a format version too, but isn't an export
+    let save_file = format!(
+        r#"{{"format_version": 1, "config": {}}}"#,
+        serde_json::to_string(&planner.config).unwrap()
+    );
+    for json in ["", "not json", r#"{"skills": []}"#, &save_file] {
+        assert!(
+            matches!(
+                planner.import_json(json),
+                Err(SchedulerError::InvalidExport(_))
+            ),
+            "{:?}",
+            json
+        );
+    }
+    assert_eq!(
+        skill_names(&planner.config.skills),
+        skill_names(&SchedulePlanner::new().config.skills)
+    );
+}
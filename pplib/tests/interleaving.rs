@@ -0,0 +1,178 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use chrono::Duration;
+use pplib::{
+    Interleaving, PracticeRecord, SchedulePlanner, SchedulerError, SessionEvent, SessionListener,
+    SessionState,
+};
+
+use common::{at, current_skill, scheduled_planner, skill_names, start_time};
+
+/// Keeps which schedule positions ended, and whether each was skipped.
+#[derive(Clone, Default, Debug)]
+struct SkillsEnded(Arc<Mutex<Vec<(usize, bool)>>>);
+
+impl SessionListener for SkillsEnded {
+    fn on_event(&mut self, event: &SessionEvent) -> Result<()> {
+        if let SessionEvent::SkillEnded {
+            skill_index,
+            skipped,
+            ..
+        } = event
+        {
+            self.0.lock().unwrap().push((*skill_index, *skipped));
+        }
+        Ok(())
+    }
+}
+
+/// A planner practicing Scales and Chords in 2 rounds of 3 minutes each,
+/// started at `start_time`.
+fn interleaved_planner(skills_ended: &SkillsEnded) -> SchedulePlanner {
+    let mut planner = scheduled_planner(&["Scales", "Chords"]);
+    planner
+        .set_interleaving(Some(Interleaving {
+            rounds: 2,
+            block_time: Duration::minutes(3),
+        }))
+        .unwrap();
+    planner.add_session_listener(Box::new(skills_ended.clone()));
+    planner.start_daily_practice(start_time()).unwrap();
+    planner
+}
+
+fn minutes_practiced(record: &PracticeRecord) -> Vec<(&str, i64)> {
+    record
+        .durations
+        .iter()
+        .map(|(name, d)| (name.as_str(), d.num_minutes()))
+        .collect()
+}
+
+#[test]
+fn skills_are_cycled_through_in_rounds() {
+    let skills_ended = SkillsEnded::default();
+    let mut planner = interleaved_planner(&skills_ended);
+
+    let ps = planner.practice_session.as_ref().unwrap();
+    assert_eq!(
+        skill_names(&ps.schedule),
+        vec!["Scales", "Chords", "Scales", "Chords"]
+    );
+    assert_eq!(ps.rounds(), 2);
+    assert_eq!(
+        (0..4).map(|idx| ps.round_of(idx)).collect::<Vec<_>>(),
+        vec![0, 0, 1, 1]
+    );
+    assert_eq!(ps.time_left, Duration::minutes(3));
+
+    for (time, idx) in [(at(3, 0), 1), (at(6, 0), 2), (at(9, 0), 3)] {
+        planner.tick_practice_session(time).unwrap();
+        let ps = planner.practice_session.as_ref().unwrap();
+        assert_eq!(ps.get_current_skill_idx(), idx);
+        assert_eq!(ps.current_round(), idx / 2);
+        assert_eq!(ps.time_left, Duration::minutes(3));
+    }
+    assert_eq!(
+        planner.tick_practice_session(at(12, 0)).unwrap(),
+        SessionState::Finished
+    );
+
+    // the history lists each skill once, with its time totalled over rounds
+    let record = planner.history.get(&at(12, 0)).unwrap();
+    assert_eq!(skill_names(&record.skills), vec!["Scales", "Chords"]);
+    assert_eq!(
+        minutes_practiced(record),
+        vec![("Chords", 6), ("Scales", 6)]
+    );
+    assert!(record.skipped.is_empty());
+}
+
+#[test]
+fn skipping_one_round_leaves_the_others_unskipped() {
+    let skills_ended = SkillsEnded::default();
+    let mut planner = interleaved_planner(&skills_ended);
+    planner.tick_practice_session(at(3, 0)).unwrap();
+    planner.skip_skill(at(4, 0)).unwrap();
+
+    let ps = planner.practice_session.as_ref().unwrap();
+    assert!(ps.is_skipped(1));
+    assert!(!ps.is_skipped(3));
+    assert!(ps.skipped_skills().is_empty());
+
+    // the second round of Chords gets its full time
+    planner.tick_practice_session(at(7, 0)).unwrap();
+    assert_eq!(current_skill(&planner), (3, "Chords".to_string()));
+    let ps = planner.practice_session.as_ref().unwrap();
+    assert_eq!(ps.time_left, Duration::minutes(3));
+    assert_eq!(
+        planner.tick_practice_session(at(10, 0)).unwrap(),
+        SessionState::Finished
+    );
+
+    assert_eq!(
+        *skills_ended.0.lock().unwrap(),
+        vec![(0, false), (1, true), (2, false), (3, false)]
+    );
+    let record = planner.history.get(&at(10, 0)).unwrap();
+    assert_eq!(
+        minutes_practiced(record),
+        vec![("Chords", 4), ("Scales", 6)]
+    );
+    assert!(record.skipped.is_empty());
+}
+
+#[test]
+fn a_skill_skipped_in_every_round_is_recorded_as_skipped() {
+    let skills_ended = SkillsEnded::default();
+    let mut planner = interleaved_planner(&skills_ended);
+    planner.tick_practice_session(at(3, 0)).unwrap();
+    planner.skip_skill(at(4, 0)).unwrap();
+    planner.tick_practice_session(at(7, 0)).unwrap();
+    planner.skip_skill(at(8, 0)).unwrap();
+
+    let record = planner.history.get(&at(8, 0)).unwrap();
+    assert_eq!(
+        minutes_practiced(record),
+        vec![("Chords", 2), ("Scales", 6)]
+    );
+    assert_eq!(
+        record.skipped.iter().cloned().collect::<Vec<_>>(),
+        vec!["Chords"]
+    );
+}
+
+#[test]
+fn going_back_only_unskips_that_round() {
+    let skills_ended = SkillsEnded::default();
+    let mut planner = interleaved_planner(&skills_ended);
+    planner.tick_practice_session(at(3, 0)).unwrap();
+    planner.skip_skill(at(4, 0)).unwrap();
+    planner.skip_skill(at(5, 0)).unwrap();
+    planner.go_back_skill(at(6, 0)).unwrap();
+
+    assert_eq!(current_skill(&planner), (2, "Scales".to_string()));
+    let ps = planner.practice_session.as_ref().unwrap();
+    assert!(ps.is_skipped(1));
+    assert!(!ps.is_skipped(2));
+    // the second round of Scales resumes with the minute it was practiced
+    assert_eq!(ps.time_left, Duration::minutes(2));
+}
+
+#[test]
+fn interleaving_needs_several_rounds_of_some_time() {
+    let mut planner = scheduled_planner(&["Scales", "Chords"]);
+    for (rounds, minutes) in [(1, 3), (2, 0)] {
+        assert!(matches!(
+            planner.set_interleaving(Some(Interleaving {
+                rounds,
+                block_time: Duration::minutes(minutes),
+            })),
+            Err(SchedulerError::InvalidInterleaving(_))
+        ));
+    }
+    assert!(planner.config.interleaving.is_none());
+}
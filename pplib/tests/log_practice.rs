@@ -0,0 +1,51 @@
+mod common;
+
+use chrono::{Duration, NaiveDate, TimeZone, Utc};
+use pplib::{SchedulePlanner, SchedulerError};
+
+use common::start_time;
+
+#[test]
+fn logs_several_entries_on_one_past_day() {
+    let now = start_time();
+    let day = NaiveDate::from_ymd(2022, 2, 27);
+    let mut planner = SchedulePlanner::new();
+
+    let first = planner.past_log_time(day);
+    planner
+        .log_practice(first, &[("Scales".to_string(), Duration::minutes(20))], now)
+        .unwrap();
+    let second = planner.past_log_time(day);
+    planner
+        .log_practice(
+            second,
+            &[("Chords".to_string(), Duration::minutes(10))],
+            now,
+        )
+        .unwrap();
+
+    assert_eq!(first, Utc.ymd(2022, 2, 27).and_hms(12, 0, 0));
+    assert!(second > first);
+    assert_eq!(second.date().naive_utc(), day);
+    let sessions = planner.sessions_n_days_back(7, now).unwrap();
+    let day_sessions = &sessions[&Utc.ymd(2022, 2, 27)];
+    let practiced = day_sessions
+        .iter()
+        .map(|(_, record)| record.skills[0].skill_name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(practiced, vec!["Scales", "Chords"]);
+}
+
+#[test]
+fn refuses_two_entries_at_the_same_time() {
+    let now = start_time();
+    let time = Utc.ymd(2022, 2, 27).and_hms(12, 0, 0);
+    let mut planner = SchedulePlanner::new();
+    let scales = [("Scales".to_string(), Duration::minutes(20))];
+
+    planner.log_practice(time, &scales, now).unwrap();
+    assert!(matches!(
+        planner.log_practice(time, &scales, now),
+        Err(SchedulerError::InvalidPracticeLog(_))
+    ));
+}
@@ -0,0 +1,242 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use chrono::Duration;
+use pplib::{PracticeSession, SchedulePlanner, SessionState};
+
+use common::{at, current_skill, practicing_planner, skill, skill_names, start_time};
+
+/// A session of two skills practiced for 5 minutes each.
+fn session() -> PracticeSession {
+    PracticeSession::new(
+        vec![skill("Scales"), skill("Chords")],
+        Duration::minutes(5),
+        BTreeMap::new(),
+        start_time(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn starts_running_with_the_full_practice_time() {
+    let mut ps = session();
+    assert_eq!(ps.state(), SessionState::NotStarted);
+    assert_eq!(ps.tick(at(1, 0)), SessionState::NotStarted);
+
+    ps.start(start_time()).unwrap();
+    assert_eq!(ps.state(), SessionState::Running);
+    assert_eq!(ps.time_left, Duration::minutes(5));
+    assert!(ps.start(at(1, 0)).is_err());
+}
+
+#[test]
+fn tick_counts_down_the_time_left() {
+    let mut ps = session();
+    ps.start(start_time()).unwrap();
+
+    assert_eq!(ps.tick(at(2, 30)), SessionState::Running);
+    assert_eq!(ps.time_left, Duration::seconds(150));
+    assert_eq!(
+        ps.time_practiced("Scales", at(2, 30)),
+        Duration::seconds(150)
+    );
+}
+
+#[test]
+fn paused_time_is_not_counted() {
+    let mut ps = session();
+    ps.start(start_time()).unwrap();
+    ps.tick(at(1, 0));
+    ps.pause(at(1, 0)).unwrap();
+    assert!(ps.is_paused());
+
+    // the timer stands still while paused
+    assert_eq!(ps.tick(at(3, 0)), SessionState::Paused);
+    assert_eq!(ps.time_left, Duration::minutes(4));
+
+    ps.resume(at(3, 0)).unwrap();
+    assert_eq!(ps.state(), SessionState::Running);
+    assert_eq!(ps.time_left, Duration::minutes(4));
+
+    ps.tick(at(4, 0));
+    assert_eq!(ps.time_left, Duration::minutes(3));
+    assert_eq!(ps.time_practiced("Scales", at(4, 0)), Duration::minutes(2));
+}
+
+#[test]
+fn several_pauses_add_up() {
+    let mut ps = session();
+    ps.start(start_time()).unwrap();
+    ps.pause(at(1, 0)).unwrap();
+    ps.resume(at(2, 0)).unwrap();
+    ps.pause(at(3, 0)).unwrap();
+    ps.resume(at(3, 30)).unwrap();
+
+    ps.tick(at(4, 0));
+    assert_eq!(ps.time_left, Duration::seconds(150));
+    assert_eq!(
+        ps.time_practiced("Scales", at(4, 0)),
+        Duration::seconds(150)
+    );
+}
+
+#[test]
+fn pause_and_resume_are_only_allowed_from_the_right_state() {
+    let mut ps = session();
+    assert!(ps.pause(start_time()).is_err());
+    assert!(ps.resume(start_time()).is_err());
+
+    ps.start(start_time()).unwrap();
+    assert!(ps.resume(at(1, 0)).is_err());
+    ps.pause(at(1, 0)).unwrap();
+    assert!(ps.pause(at(2, 0)).is_err());
+}
+
+#[test]
+fn skill_completes_when_its_time_runs_out() {
+    let mut ps = session();
+    ps.start(start_time()).unwrap();
+
+    assert_eq!(ps.tick(at(4, 59)), SessionState::Running);
+    assert_eq!(ps.tick(at(5, 0)), SessionState::SkillComplete);
+    assert_eq!(ps.time_left, Duration::seconds(0));
+    // a late tick doesn't count past the skill's time
+    assert_eq!(ps.time_practiced("Scales", at(7, 0)), Duration::minutes(5));
+}
+
+#[test]
+fn overtime_counts_past_the_skills_time() {
+    let mut ps = session();
+    ps.overtime = true;
+    ps.start(start_time()).unwrap();
+
+    assert_eq!(ps.tick(at(6, 30)), SessionState::Running);
+    assert_eq!(ps.time_left, Duration::seconds(0));
+    assert_eq!(ps.time_over, Duration::seconds(90));
+    assert_eq!(
+        ps.time_practiced("Scales", at(6, 30)),
+        Duration::seconds(390)
+    );
+}
+
+#[test]
+fn resuming_after_the_time_ran_out_completes_the_skill() {
+    let mut ps = session();
+    ps.start(start_time()).unwrap();
+    ps.pause(at(4, 0)).unwrap();
+
+    // only the minute before the pause is left to practice
+    ps.resume(at(10, 0)).unwrap();
+    assert_eq!(ps.tick(at(10, 59)), SessionState::Running);
+    assert_eq!(ps.tick(at(11, 0)), SessionState::SkillComplete);
+}
+
+fn skipped(planner: &SchedulePlanner) -> Vec<usize> {
+    let ps = planner.practice_session.as_ref().unwrap();
+    ps.skipped.iter().cloned().collect()
+}
+
+#[test]
+fn skipping_moves_on_and_marks_the_skill_skipped() {
+    let mut planner = practicing_planner();
+    planner.tick_practice_session(at(1, 0)).unwrap();
+    planner.skip_skill(at(1, 0)).unwrap();
+
+    assert_eq!(current_skill(&planner), (1, "Chords".to_string()));
+    assert_eq!(skipped(&planner), vec![0]);
+    let ps = planner.practice_session.as_ref().unwrap();
+    assert_eq!(ps.time_left, Duration::minutes(5));
+    assert_eq!(ps.time_practiced("Scales", at(1, 0)), Duration::minutes(1));
+}
+
+#[test]
+fn going_back_resumes_the_previous_skill() {
+    let mut planner = practicing_planner();
+    assert!(planner.go_back_skill(start_time()).is_err());
+
+    planner.skip_skill(at(1, 0)).unwrap();
+    planner.go_back_skill(at(2, 0)).unwrap();
+
+    assert_eq!(current_skill(&planner), (0, "Scales".to_string()));
+    // going back to a skill means it's no longer skipped
+    assert!(skipped(&planner).is_empty());
+    let ps = planner.practice_session.as_ref().unwrap();
+    assert_eq!(ps.time_left, Duration::minutes(4));
+    assert_eq!(ps.time_practiced("Chords", at(2, 0)), Duration::minutes(1));
+}
+
+#[test]
+fn only_upcoming_skills_can_be_reordered() {
+    let mut planner = practicing_planner();
+    assert!(planner.move_remaining_skill(0, 2).is_err());
+    assert!(planner.move_remaining_skill(1, 3).is_err());
+
+    planner.move_remaining_skill(2, 1).unwrap();
+    let ps = planner.practice_session.as_ref().unwrap();
+    assert_eq!(
+        skill_names(&ps.schedule),
+        vec!["Scales", "Arpeggios", "Chords"]
+    );
+    assert_eq!(
+        skill_names(planner.todays_schedule.as_ref().unwrap()),
+        vec!["Scales", "Arpeggios", "Chords"]
+    );
+
+    planner.skip_skill(at(1, 0)).unwrap();
+    assert_eq!(current_skill(&planner), (1, "Arpeggios".to_string()));
+}
+
+#[test]
+fn extending_adds_to_the_current_skills_time() {
+    let mut planner = practicing_planner();
+    planner.tick_practice_session(at(3, 0)).unwrap();
+    planner
+        .extend_skill(Duration::minutes(2), at(3, 0))
+        .unwrap();
+
+    let ps = planner.practice_session.as_ref().unwrap();
+    assert_eq!(ps.time_left, Duration::minutes(4));
+    assert_eq!(
+        planner.tick_practice_session(at(6, 59)).unwrap(),
+        SessionState::Running
+    );
+    planner.tick_practice_session(at(7, 0)).unwrap();
+    assert_eq!(current_skill(&planner), (1, "Chords".to_string()));
+}
+
+#[test]
+fn history_records_the_time_actually_practiced() {
+    let mut planner = practicing_planner();
+    planner.move_remaining_skill(2, 1).unwrap();
+    planner.skip_skill(at(1, 0)).unwrap();
+    planner.go_back_skill(at(2, 0)).unwrap();
+    planner
+        .extend_skill(Duration::minutes(2), at(3, 0))
+        .unwrap();
+
+    // Scales runs out after 7 minutes, 1 of them before the skip
+    planner.tick_practice_session(at(8, 0)).unwrap();
+    assert_eq!(current_skill(&planner), (1, "Arpeggios".to_string()));
+    planner.skip_skill(at(9, 0)).unwrap();
+    assert_eq!(current_skill(&planner), (2, "Chords".to_string()));
+    assert_eq!(
+        planner.tick_practice_session(at(14, 0)).unwrap(),
+        SessionState::Finished
+    );
+
+    let record = planner.history.get(&at(14, 0)).unwrap();
+    let durations = record
+        .durations
+        .iter()
+        .map(|(name, d)| (name.as_str(), d.num_minutes()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        durations,
+        vec![("Arpeggios", 2), ("Chords", 5), ("Scales", 7)]
+    );
+    assert_eq!(
+        record.skipped.iter().cloned().collect::<Vec<_>>(),
+        vec!["Arpeggios"]
+    );
+}
@@ -0,0 +1,69 @@
+use std::path::Path;
+
+use pplib::{profile_save_path, PracticeProfiles, SchedulerError, DEFAULT_PROFILE};
+
+#[test]
+fn profiles_are_added_and_switched_by_name() {
+    let mut profiles = PracticeProfiles::default();
+    assert!(profiles.is_default());
+
+    profiles.add_profile("Bass").unwrap();
+    assert!(matches!(
+        profiles.add_profile("Bass"),
+        Err(SchedulerError::ProfileExists(name)) if name == "Bass"
+    ));
+    assert!(matches!(
+        profiles.add_profile("../Bass"),
+        Err(SchedulerError::InvalidProfileName(_))
+    ));
+
+    assert!(matches!(
+        profiles.switch_profile("Drums"),
+        Err(SchedulerError::UnknownProfile(name)) if name == "Drums"
+    ));
+    assert!(profiles.is_default());
+    profiles.switch_profile("Bass").unwrap();
+    assert_eq!(profiles.active_profile, "Bass");
+    assert!(!profiles.is_default());
+}
+
+#[test]
+fn only_an_inactive_profile_can_be_removed() {
+    let mut profiles = PracticeProfiles::default();
+    profiles.add_profile("Bass").unwrap();
+    profiles.add_profile("Drums").unwrap();
+    profiles.switch_profile("Bass").unwrap();
+
+    assert!(matches!(
+        profiles.remove_profile("Piano"),
+        Err(SchedulerError::UnknownProfile(_))
+    ));
+    assert!(matches!(
+        profiles.remove_profile(DEFAULT_PROFILE),
+        Err(SchedulerError::DefaultProfileRemoval())
+    ));
+    assert!(matches!(
+        profiles.remove_profile("Bass"),
+        Err(SchedulerError::ActiveProfileRemoval())
+    ));
+
+    profiles.remove_profile("Drums").unwrap();
+    assert_eq!(
+        profiles.profiles.iter().collect::<Vec<_>>(),
+        vec!["Bass", DEFAULT_PROFILE]
+    );
+}
+
+#[test]
+fn the_default_profile_keeps_the_original_save_file() {
+    let data_dir = Path::new("/data/ppcli");
+
+    assert_eq!(
+        profile_save_path(data_dir, DEFAULT_PROFILE),
+        Path::new("/data/ppcli/history.bin")
+    );
+    assert_eq!(
+        profile_save_path(data_dir, "Bass"),
+        Path::new("/data/ppcli/profiles/Bass/history.bin")
+    );
+}
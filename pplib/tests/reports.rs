@@ -0,0 +1,39 @@
+use chrono::{Duration, NaiveDate, TimeZone, Utc};
+use pplib::SchedulePlanner;
+
+#[test]
+fn minutes_are_rounded_down_only_once_summed() {
+    let now = Utc.ymd(2022, 3, 10).and_hms(18, 0, 0);
+    let mut planner = SchedulePlanner::new();
+    let seconds = |name: &str, seconds: i64| (name.to_string(), Duration::seconds(seconds));
+    // 3m50s and 2m40s of Scales, and 1m30s of Chords
+    planner
+        .log_practice(
+            Utc.ymd(2022, 3, 8).and_hms(12, 0, 0),
+            &[seconds("Scales", 230), seconds("Chords", 90)],
+            now,
+        )
+        .unwrap();
+    planner
+        .log_practice(
+            Utc.ymd(2022, 3, 9).and_hms(12, 0, 0),
+            &[seconds("Scales", 160)],
+            now,
+        )
+        .unwrap();
+
+    let report = planner.report(
+        NaiveDate::from_ymd(2022, 3, 1),
+        NaiveDate::from_ymd(2022, 3, 10),
+        now,
+    );
+    let minutes = report
+        .skills
+        .iter()
+        .map(|s| (s.skill_name.as_str(), s.minutes, s.sessions))
+        .collect::<Vec<_>>();
+    assert_eq!(minutes, vec![("Scales", 6, 2), ("Chords", 1, 1)]);
+    // 8 minutes in all, more than the skills' rounded minutes add up to
+    assert_eq!(report.total_minutes, 8);
+    assert_eq!(report.days_practiced, 2);
+}
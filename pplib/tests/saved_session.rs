@@ -0,0 +1,142 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, Utc};
+use pplib::{PracticeSession, SavedSession, SchedulePlanner, SessionState};
+
+use common::{at, current_skill, practicing_planner, scheduled_planner, skill_names, start_time};
+
+/// A planner with the same skills as `practicing_planner`, not practicing.
+fn idle_planner() -> SchedulePlanner {
+    let mut planner = scheduled_planner(&["Scales", "Chords", "Arpeggios"]);
+    planner.todays_schedule = None;
+    planner.todays_schedule_time = None;
+    planner
+}
+
+/// Saves `planner`'s session and reads it back, as after a restart.
+fn save_and_load(planner: &SchedulePlanner, saved_at: DateTime<Utc>) -> SavedSession {
+    let json = planner.save_session(saved_at).unwrap().to_json().unwrap();
+    SavedSession::from_json(&json).unwrap()
+}
+
+#[test]
+fn a_saved_session_is_restored_where_it_was_left() {
+    let mut planner = practicing_planner();
+    planner.skip_skill(at(1, 0)).unwrap();
+    planner.tick_practice_session(at(3, 0)).unwrap();
+
+    let saved = save_and_load(&planner, at(3, 0));
+    assert_eq!(saved.saved_at, at(3, 0));
+    assert_eq!(saved.start_time(), start_time());
+    assert_eq!(saved.current_skill_name(), "Chords");
+
+    let mut restored = idle_planner();
+    restored.restore_session(saved).unwrap();
+    assert!(restored.practicing);
+    assert_eq!(current_skill(&restored), (1, "Chords".to_string()));
+    assert_eq!(
+        skill_names(restored.todays_schedule.as_ref().unwrap()),
+        vec!["Scales", "Chords", "Arpeggios"]
+    );
+    let ps = restored.practice_session.as_ref().unwrap();
+    assert_eq!(ps.time_left, Duration::minutes(3));
+    assert_eq!(ps.time_practiced("Scales", at(3, 0)), Duration::minutes(1));
+    assert!(ps.is_skipped(0));
+}
+
+#[test]
+fn a_restored_session_is_paused_at_the_time_it_was_saved() {
+    let mut planner = practicing_planner();
+    planner.tick_practice_session(at(2, 0)).unwrap();
+    let saved = save_and_load(&planner, at(2, 0));
+
+    let mut restored = idle_planner();
+    restored.restore_session(saved).unwrap();
+    assert_eq!(
+        restored.tick_practice_session(at(30, 0)).unwrap(),
+        SessionState::Paused
+    );
+
+    // the time between saving and resuming isn't practice
+    restored.resume_practice_session(at(30, 0)).unwrap();
+    restored.tick_practice_session(at(31, 0)).unwrap();
+    let ps = restored.practice_session.as_ref().unwrap();
+    assert_eq!(ps.time_left, Duration::minutes(2));
+    assert_eq!(ps.time_practiced("Scales", at(31, 0)), Duration::minutes(3));
+}
+
+#[test]
+fn a_session_saved_while_paused_stays_paused_from_when_it_was_paused() {
+    let mut planner = practicing_planner();
+    planner.pause_practice_session(at(1, 0)).unwrap();
+    let saved = save_and_load(&planner, at(4, 0));
+
+    let mut restored = idle_planner();
+    restored.restore_session(saved).unwrap();
+    restored.resume_practice_session(at(10, 0)).unwrap();
+    restored.tick_practice_session(at(11, 0)).unwrap();
+    let ps = restored.practice_session.as_ref().unwrap();
+    assert_eq!(ps.time_left, Duration::minutes(3));
+}
+
+#[test]
+fn nothing_is_saved_without_a_session_underway() {
+    let mut planner = idle_planner();
+    assert!(planner.save_session(start_time()).is_none());
+
+    planner = practicing_planner();
+    for time in [at(5, 0), at(10, 0), at(15, 0)] {
+        planner.tick_practice_session(time).unwrap();
+    }
+    assert!(!planner.practicing);
+    assert!(planner.save_session(at(15, 0)).is_none());
+}
+
+#[test]
+fn a_session_is_not_restored_over_one_underway() {
+    let saved = save_and_load(&practicing_planner(), at(1, 0));
+
+    let mut planner = practicing_planner();
+    assert!(planner.restore_session(saved).is_err());
+    assert_eq!(current_skill(&planner), (0, "Scales".to_string()));
+    assert_eq!(
+        planner.practice_session.as_ref().unwrap().state(),
+        SessionState::Running
+    );
+}
+
+#[test]
+fn only_a_session_underway_is_restored() {
+    // a session that never started
+    let mut not_started = idle_planner();
+    not_started.practice_session = Some(
+        PracticeSession::new(
+            not_started.config.skills.clone(),
+            Duration::minutes(5),
+            BTreeMap::new(),
+            start_time(),
+        )
+        .unwrap(),
+    );
+    not_started.practicing = true;
+
+    // a session whose last skill was completed
+    let mut finished = practicing_planner();
+    for time in [at(5, 0), at(10, 0), at(15, 0)] {
+        finished.tick_practice_session(time).unwrap();
+    }
+    finished.practicing = true;
+
+    for saved in [
+        save_and_load(&not_started, at(1, 0)),
+        save_and_load(&finished, at(16, 0)),
+    ] {
+        let mut planner = idle_planner();
+        assert!(planner.restore_session(saved).is_err());
+        assert!(!planner.practicing);
+        assert!(planner.practice_session.is_none());
+        assert!(planner.todays_schedule.is_none());
+    }
+}
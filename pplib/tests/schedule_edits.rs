@@ -0,0 +1,79 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use chrono::Duration;
+use pplib::{PracticeSession, SchedulePlanner, SchedulerError};
+
+use common::{skill_names, skills, start_time};
+
+/// A planner with today's schedule picked from just two skills.
+fn two_skill_planner() -> SchedulePlanner {
+    let mut planner = SchedulePlanner::new();
+    planner.config.skills = skills(&["Scales", "Chords"]);
+    planner.config.skills_per_day = 2;
+    planner.update_todays_schedule(false, start_time()).unwrap();
+    planner
+}
+
+fn todays_skill_names(planner: &SchedulePlanner) -> Vec<String> {
+    skill_names(planner.todays_schedule.as_deref().unwrap_or_default())
+}
+
+#[test]
+fn the_last_skill_left_today_cannot_be_excluded() {
+    let mut planner = two_skill_planner();
+    let first = todays_skill_names(&planner)[0].clone();
+
+    // there is nothing to replace it with, so its slot is dropped
+    assert!(planner
+        .exclude_skill(&first, start_time())
+        .unwrap()
+        .is_none());
+    let remaining = todays_skill_names(&planner);
+    assert_eq!(remaining.len(), 1);
+
+    assert!(matches!(
+        planner.exclude_skill(&remaining[0], start_time()),
+        Err(SchedulerError::InvalidScheduleEdit(_))
+    ));
+    assert_eq!(todays_skill_names(&planner), remaining);
+    assert!(!planner.todays_excluded.contains(&remaining[0]));
+
+    planner.start_daily_practice(start_time()).unwrap();
+    let ps = planner.practice_session.as_ref().unwrap();
+    assert_eq!(ps.current_skill.skill_name, remaining[0]);
+}
+
+#[test]
+fn shuffling_with_every_skill_excluded_keeps_the_schedule() {
+    let mut planner = two_skill_planner();
+    let before = todays_skill_names(&planner);
+    planner.todays_excluded = before.iter().cloned().collect();
+
+    assert!(matches!(
+        planner.update_todays_schedule(true, start_time()),
+        Err(SchedulerError::MissingSkills())
+    ));
+    assert_eq!(todays_skill_names(&planner), before);
+}
+
+#[test]
+fn practice_does_not_start_without_skills() {
+    let mut planner = two_skill_planner();
+    planner.todays_schedule = Some(vec![]);
+
+    let err = planner.start_daily_practice(start_time()).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<SchedulerError>(),
+        Some(SchedulerError::MissingSkills())
+    ));
+    assert!(!planner.practicing);
+    assert!(planner.practice_session.is_none());
+}
+
+#[test]
+fn a_session_needs_a_skill_to_practice() {
+    let session = PracticeSession::new(vec![], Duration::minutes(5), BTreeMap::new(), start_time());
+    assert!(matches!(session, Err(SchedulerError::MissingSkills())));
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use chrono::Duration;
+use pplib::{PracticeSegment, SchedulePlanner, SchedulerError, SegmentKind, SessionState};
+
+use common::{at, scheduled_planner, skill_names, start_time};
+
+fn segment(skill_name: &str, minutes: i64) -> Option<PracticeSegment> {
+    Some(PracticeSegment {
+        skill_name: skill_name.to_string(),
+        practice_time: Duration::minutes(minutes),
+    })
+}
+
+/// A planner with a 2 minute warm-up and a 3 minute cool-down around two
+/// skills practiced for 5 minutes each.
+fn planner_with_segments() -> SchedulePlanner {
+    let mut planner = scheduled_planner(&["Warm Up", "Scales", "Chords", "Cool Down"]);
+    planner
+        .set_segment(SegmentKind::WarmUp, segment("Warm Up", 2))
+        .unwrap();
+    planner
+        .set_segment(SegmentKind::CoolDown, segment("Cool Down", 3))
+        .unwrap();
+    planner
+}
+
+#[test]
+fn segments_wrap_the_scheduled_skills_for_their_own_time() {
+    let mut planner = planner_with_segments();
+    // today's schedule had the segments' skills in it, so it's picked again
+    assert!(planner.todays_schedule.is_none());
+
+    planner.start_daily_practice(start_time()).unwrap();
+    let ps = planner.practice_session.as_ref().unwrap();
+    let schedule = skill_names(&ps.schedule);
+    assert_eq!(schedule.len(), 4);
+    assert_eq!(schedule[0], "Warm Up");
+    assert_eq!(schedule[3], "Cool Down");
+    assert_eq!(ps.time_left, Duration::minutes(2));
+    assert_eq!(
+        skill_names(&ps.scheduled_skills()),
+        skill_names(planner.todays_schedule.as_ref().unwrap())
+    );
+
+    planner.tick_practice_session(at(2, 0)).unwrap();
+    let ps = planner.practice_session.as_ref().unwrap();
+    assert_eq!(ps.get_current_skill_idx(), 1);
+    assert_eq!(ps.time_left, Duration::minutes(5));
+
+    planner.tick_practice_session(at(7, 0)).unwrap();
+    planner.tick_practice_session(at(12, 0)).unwrap();
+    let ps = planner.practice_session.as_ref().unwrap();
+    assert_eq!(ps.current_skill.skill_name, "Cool Down");
+    assert_eq!(ps.time_left, Duration::minutes(3));
+    assert_eq!(
+        planner.tick_practice_session(at(15, 0)).unwrap(),
+        SessionState::Finished
+    );
+}
+
+#[test]
+fn segment_skills_are_never_picked() {
+    let mut planner = planner_with_segments();
+
+    for _ in 0..20 {
+        planner.update_todays_schedule(true, start_time()).unwrap();
+        let mut picked = skill_names(planner.todays_schedule.as_ref().unwrap());
+        picked.sort();
+        assert_eq!(picked, vec!["Chords", "Scales"]);
+    }
+    assert_eq!(
+        planner
+            .scheduled_skills()
+            .map(|s| s.skill_name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["Scales", "Chords"]
+    );
+}
+
+#[test]
+fn segments_are_left_out_of_the_history() {
+    let mut planner = planner_with_segments();
+    planner.start_daily_practice(start_time()).unwrap();
+    // skip the warm-up partway through, and let the rest run out
+    planner.skip_skill(at(1, 0)).unwrap();
+    for time in [at(6, 0), at(11, 0), at(14, 0)] {
+        planner.tick_practice_session(time).unwrap();
+    }
+
+    let record = planner.history.get(&at(14, 0)).unwrap();
+    let mut practiced = skill_names(&record.skills);
+    practiced.sort();
+    assert_eq!(practiced, vec!["Chords", "Scales"]);
+    assert_eq!(
+        record.durations.keys().collect::<Vec<_>>(),
+        vec!["Chords", "Scales"]
+    );
+    assert!(record
+        .durations
+        .values()
+        .all(|d| *d == Duration::minutes(5)));
+    assert!(record.skipped.is_empty());
+}
+
+#[test]
+fn a_segment_needs_its_own_skill_and_some_time() {
+    let mut planner = planner_with_segments();
+
+    assert!(matches!(
+        planner.set_segment(SegmentKind::WarmUp, segment("Arpeggios", 2)),
+        Err(SchedulerError::UnknownSkill(_))
+    ));
+    assert!(matches!(
+        planner.set_segment(SegmentKind::WarmUp, segment("Scales", 0)),
+        Err(SchedulerError::InvalidSegment(_))
+    ));
+    assert!(matches!(
+        planner.set_segment(SegmentKind::WarmUp, segment("Cool Down", 2)),
+        Err(SchedulerError::InvalidSegment(_))
+    ));
+    assert_eq!(planner.config.warm_up, segment("Warm Up", 2));
+
+    planner.set_segment(SegmentKind::WarmUp, None).unwrap();
+    assert!(!planner.is_segment_skill("Warm Up"));
+    assert_eq!(planner.scheduled_skills().count(), 3);
+}
@@ -0,0 +1,129 @@
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{Duration, TimeZone, Utc};
+use pplib::{SchedulePlanner, SAVE_FORMAT_VERSION};
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+/// A path to save to that no other test uses.
+fn save_path(test_name: &str) -> PathBuf {
+    std::env::temp_dir()
+        .join(format!("pplib-storage-{}", std::process::id()))
+        .join(test_name)
+        .join("history.bin")
+}
+
+#[test]
+fn loads_a_save_file_from_before_versioning() {
+    let planner = SchedulePlanner::new_from_disk(&fixture("unversioned.bin")).unwrap();
+
+    assert_eq!(planner.config.skill_practice_time, Duration::minutes(10));
+    assert_eq!(planner.config.skills_per_day, 3);
+    assert_eq!(planner.config.skill_repeat_days, 2);
+    let skills = planner
+        .config
+        .skills
+        .iter()
+        .map(|s| s.skill_name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        skills,
+        vec![
+            "Ear Training",
+            "Left Hand Exercises",
+            "Alternate Picking Exercises",
+            "Chords",
+            "Scales"
+        ]
+    );
+    assert!(planner.history.is_empty());
+    assert!(planner.todays_schedule.is_none());
+}
+
+#[test]
+fn loads_a_save_file_from_before_later_settings_existed() {
+    let planner =
+        SchedulePlanner::new_from_disk(&fixture("format_1_before_overtime.json")).unwrap();
+
+    assert_eq!(planner.config.skill_practice_time, Duration::minutes(15));
+    assert_eq!(planner.config.skills.len(), 3);
+    assert!(!planner.config.overtime);
+    assert!(planner.config.warm_up.is_none());
+    assert!(planner.config.cool_down.is_none());
+    assert!(planner.config.rest_breaks.is_none());
+    assert!(!planner.config.exclude_practiced_today);
+    assert!(planner.config.interleaving.is_none());
+    assert!(planner.todays_schedule.is_none());
+    assert!(planner.todays_pinned.is_empty());
+    assert!(planner.todays_excluded.is_empty());
+
+    let records = planner.history.values().collect::<Vec<_>>();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].notes["Scales"], "Slow and even");
+    assert!(records[0].durations.is_empty());
+    assert!(records[0].skipped.is_empty());
+    assert_eq!(records[1].durations["Chords"], Duration::minutes(14));
+}
+
+#[test]
+fn saves_and_loads_the_planner() {
+    let now = Utc.ymd(2022, 3, 1).and_hms(18, 0, 0);
+    let mut planner = SchedulePlanner::new();
+    planner.config.skills_per_day = 2;
+    planner
+        .log_practice(
+            now - Duration::days(1),
+            &[("Scales".to_string(), Duration::minutes(12))],
+            now,
+        )
+        .unwrap();
+    planner.update_todays_schedule(false, now).unwrap();
+    let pinned = planner.todays_schedule.as_ref().unwrap()[0]
+        .skill_name
+        .clone();
+    planner.pin_skill(&pinned, true).unwrap();
+
+    let path = save_path("saves_and_loads_the_planner");
+    planner.save_to_disk(&path).unwrap();
+    let loaded = SchedulePlanner::new_from_disk(&path).unwrap();
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+    assert_eq!(loaded.config, planner.config);
+    assert_eq!(loaded.history, planner.history);
+    assert_eq!(loaded.todays_schedule, planner.todays_schedule);
+    assert_eq!(loaded.todays_schedule_time, Some(now));
+    assert!(loaded.is_pinned(&pinned));
+}
+
+#[test]
+fn refuses_a_save_file_from_a_newer_version() {
+    let path = save_path("refuses_a_save_file_from_a_newer_version");
+    SchedulePlanner::new().save_to_disk(&path).unwrap();
+    let saved = fs::read_to_string(&path).unwrap().replacen(
+        &format!("\"format_version\":{}", SAVE_FORMAT_VERSION),
+        &format!("\"format_version\":{}", SAVE_FORMAT_VERSION + 1),
+        1,
+    );
+    fs::write(&path, saved).unwrap();
+
+    let err = SchedulePlanner::new_from_disk(&path).unwrap_err();
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    assert!(err.to_string().contains("newer than this version supports"));
+}
+
+#[test]
+fn refuses_a_file_that_is_not_a_save_file() {
+    let path = save_path("refuses_a_file_that_is_not_a_save_file");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, "not a save file").unwrap();
+
+    let err = SchedulePlanner::new_from_disk(&path).unwrap_err();
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    assert!(err.to_string().contains("Unrecognized save file"));
+}
@@ -0,0 +1,30 @@
+use pplib::{SchedulePlanner, SchedulerError};
+
+#[test]
+fn restoring_an_exercise_keeps_names_unique() {
+    let mut planner = SchedulePlanner::new();
+    planner.add_skill("Transcription").unwrap();
+    planner
+        .add_exercise("Transcription", "Etude", "old")
+        .unwrap();
+    planner.archive_exercise("Transcription", "Etude").unwrap();
+    planner
+        .add_exercise("Transcription", "Etude", "new")
+        .unwrap();
+
+    assert!(matches!(
+        planner.restore_exercise(0),
+        Err(SchedulerError::ExerciseExists(name)) if name == "Etude"
+    ));
+    // the exercise stays in the trash, and the active one is untouched
+    assert_eq!(planner.config.archived_exercises.len(), 1);
+    let exercise = planner.get_exercise("Transcription", "Etude").unwrap();
+    assert_eq!(exercise.exercise_markdown_contents, "new");
+
+    planner
+        .rename_exercise("Transcription", "Etude", "Etude 2")
+        .unwrap();
+    planner.restore_exercise(0).unwrap();
+    assert!(planner.config.archived_exercises.is_empty());
+    assert!(planner.get_exercise("Transcription", "Etude").is_ok());
+}
@@ -0,0 +1,65 @@
+use pplib::{split_vextab_blocks, vextab_to_ascii, ExerciseBlock, SchedulePlanner, SchedulerError};
+
+#[test]
+fn draws_the_built_in_exercises_as_tablature() {
+    let planner = SchedulePlanner::new();
+    let mut drawn = 0;
+    for exercise in planner
+        .config
+        .skills
+        .iter()
+        .flat_map(|s| s.exercises.iter())
+    {
+        for block in split_vextab_blocks(&exercise.exercise_markdown_contents) {
+            if let ExerciseBlock::Vextab(source) = block {
+                let ascii = vextab_to_ascii(&source)
+                    .unwrap_or_else(|e| panic!("{} doesn't render: {}", exercise.exercise_name, e));
+                let first_stave = ascii.lines().take(6).collect::<Vec<_>>();
+                assert_eq!(first_stave.len(), 6, "{}", exercise.exercise_name);
+                assert!(first_stave[0].starts_with("e|"));
+                assert!(first_stave[5].starts_with("E|"));
+                drawn += 1;
+            }
+        }
+    }
+
+    assert!(drawn > 0, "no built-in exercise has a vextab block");
+}
+
+#[test]
+fn draws_notes_chords_rests_and_bars() {
+    let source = "tabstave notation=false\nnotes :q 1-3/6 | (0/1.1/2) ## 10/5";
+
+    assert_eq!(
+        vextab_to_ascii(source).unwrap(),
+        [
+            "e|------|-0--------|",
+            "B|------|-1--------|",
+            "G|------|----------|",
+            "D|------|----------|",
+            "A|------|-------10-|",
+            "E|-1--3-|----------|",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn malformed_vextab_is_an_error_on_its_line() {
+    let cases = [
+        ("notes 1/6", 1),
+        ("tabstave\nnotes 1/7", 2),
+        ("tabstave\n\nnotes q/6", 3),
+        ("tabstave\nnotes (5/2.5/3", 2),
+        ("tabstave\nnotes :z 1/6", 2),
+        ("tabstave\nnotes /", 2),
+    ];
+    for (source, line) in cases {
+        match vextab_to_ascii(source) {
+            Err(SchedulerError::InvalidVextab { line: found, .. }) => {
+                assert_eq!(found, line, "{:?}", source)
+            }
+            other => panic!("{:?} gave {:?}", source, other),
+        }
+    }
+}
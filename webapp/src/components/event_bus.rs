@@ -1,6 +1,8 @@
+use pplib::{Alert, AlertSink};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
-use yew_agent::{Agent, AgentLink, Context, HandlerId};
+use std::fmt;
+use yew_agent::{Agent, AgentLink, Context, Dispatched, Dispatcher, HandlerId};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub enum Request {
@@ -45,3 +47,30 @@ impl Agent for EventBus {
         self.subscribers.remove(&id);
     }
 }
+
+/// Sends session alerts to the audio player as a ding sound.
+pub struct EventBusAlertSink {
+    event_bus: Dispatcher<EventBus>,
+}
+
+impl EventBusAlertSink {
+    pub fn new() -> Self {
+        Self {
+            event_bus: EventBus::dispatcher(),
+        }
+    }
+}
+
+impl fmt::Debug for EventBusAlertSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventBusAlertSink").finish_non_exhaustive()
+    }
+}
+
+impl AlertSink for EventBusAlertSink {
+    fn alert(&mut self, _alert: Alert) -> anyhow::Result<()> {
+        self.event_bus
+            .send(Request::EventBusMsg("ding.wav".to_owned()));
+        Ok(())
+    }
+}
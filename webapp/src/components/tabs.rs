@@ -13,7 +13,8 @@ lazy_static! {
     pub(crate) static ref TABS: Vec<String> = vec![
         "Practice".to_string(),
         "History".to_string(),
-        "Settings".to_string()
+        "Settings".to_string(),
+        "Trash".to_string()
     ];
 }
 
@@ -2,36 +2,42 @@
 #[macro_use]
 extern crate lazy_static;
 
-use std::collections::BTreeMap;
-use std::collections::HashSet;
-use std::ops::Add;
-use std::ops::Sub;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
 use std::sync::Arc;
 
 use anyhow::Result;
 use chrono::Duration;
-use chrono::{Date, DateTime, Utc};
+use chrono::{Date, DateTime, NaiveDate, Utc};
+use gloo::file::callbacks::{read_as_text, FileReader};
+use gloo::file::{Blob, ObjectUrl};
 use gloo::storage::{LocalStorage, Storage};
 use gloo::timers::callback::Interval;
 use hhmmss::Hhmmss;
 use pplib::PracticeExercise;
 use pplib::PracticeSession;
 use pulldown_cmark::{html::push_html, Options, Parser};
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::JsCast;
 use web_sys::HtmlInputElement;
-use web_sys::{HtmlOptionElement, MouseEvent};
+use web_sys::{
+    File, HtmlElement, HtmlOptionElement, HtmlSelectElement, HtmlTextAreaElement, MouseEvent,
+};
 #[allow(unused_imports)]
 use yew::prelude::*;
 use yew::virtual_dom::VNode;
 use yew::{html, html::Scope, Classes, Component, Context, Html};
-use yew_agent::{Dispatched, Dispatcher};
 
 use crate::bindings::tablature::*;
 use crate::components::audio_player::*;
-use crate::components::event_bus::{EventBus, Request};
+use crate::components::event_bus::EventBusAlertSink;
 use crate::components::modal::*;
 use crate::components::tabs::*;
-use pplib::{PracticeSkill, SchedulePlanner};
+use pplib::{
+    AlertListener, DaySessions, Interleaving, PracticeHistory, PracticeProfiles, PracticeRecord,
+    PracticeSegment, PracticeSkill, RestBreaks, SavedSession, SchedulePlanner, SegmentKind,
+    SessionEvent, SessionListener, SessionState, DEFAULT_PROFILE,
+};
 
 mod bindings;
 mod components;
@@ -43,11 +49,27 @@ static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 const CONFIG_KEY: &str = "yew.practiceplanner.config";
 const HISTORY_KEY: &str = "yew.practiceplanner.history";
 const FIRST_PAGE_VIEW: &str = "yew.practiceplanner.first_page_view";
+const PROFILES_KEY: &str = "yew.practiceplanner.profiles";
+const SESSION_KEY: &str = "yew.practiceplanner.session";
+const TODAY_KEY: &str = "yew.practiceplanner.today";
+/// How much time the "+ Minutes" button adds to the current skill.
+const EXTEND_MINUTES: i64 = 5;
+/// How often a running session is saved between its state changes, which
+/// bounds the practice time lost to a reload.
+const SESSION_SAVE_SECONDS: i64 = 5;
 
 pub enum Msg {
     StartPracticing,
     StopPracticing,
     PracticeTick,
+    SkipSkill,
+    PreviousSkill,
+    ExtendSkill,
+    MoveSkill(usize, usize),
+    PinSkill(String, bool),
+    SwapSkill(String),
+    ExcludeSkill(String),
+    AddSkillToday(String),
     ShowResetHistoryPrompt,
     ResetHistory,
     ShuffleToday,
@@ -58,14 +80,121 @@ pub enum Msg {
     SetHelp,
     SelectSkill(HtmlOptionElement),
     SelectExercise(HtmlOptionElement),
-    ShowDeleteSkillPrompt,
+    ShowArchiveSkillPrompt,
     ShowResetSettingsPrompt,
     ResetSettings,
     SaveSettings,
-    DeleteSkill,
+    ArchiveSkill,
+    ArchiveExercise,
+    RestoreSkill(String),
+    ShowPurgeSkillPrompt(String),
+    PurgeSkill(String),
+    RestoreExercise(usize),
+    ShowPurgeExercisePrompt(usize),
+    PurgeExercise(usize),
     PausePracticing,
     NextExercise,
     PreviousExercise,
+    SwitchProfile(String),
+    AddProfile,
+    ShowDeleteProfilePrompt,
+    DeleteProfile,
+    UpdateSessionNote(String),
+    UpdateHistoryNote(DateTime<Utc>, String, String),
+    SearchNotes(String),
+    ExportData,
+    ImportFile(Option<File>),
+    ImportData(Result<String, String>),
+    LogPractice,
+    SessionEvent(SessionEvent),
+    ResumeSavedSession,
+    DiscardSavedSession,
+}
+
+/// LocalStorage key for `key` within a profile. The default profile keeps the
+/// original keys so data saved before profiles existed is still found.
+fn profile_key(profile: &str, key: &str) -> String {
+    if profile == DEFAULT_PROFILE {
+        return key.to_string();
+    }
+
+    format!("{}.{}", key, profile)
+}
+
+/// Loads a profile's history, upgrading history saved before it could hold
+/// journal notes.
+fn load_history(profile: &str) -> gloo::storage::Result<PracticeHistory> {
+    let key = profile_key(profile, HISTORY_KEY);
+    LocalStorage::get(&key).or_else(|_| {
+        let legacy: BTreeMap<DateTime<Utc>, Vec<Arc<PracticeSkill>>> = LocalStorage::get(&key)?;
+        Ok(legacy
+            .into_iter()
+            .map(|(time, skills)| (time, PracticeRecord::from(skills)))
+            .collect())
+    })
+}
+
+/// Today's schedule along with the skills pinned in it and excluded from it,
+/// saved so they survive a reload.
+#[derive(Serialize, Deserialize)]
+struct SavedToday {
+    schedule: Option<Vec<Arc<PracticeSkill>>>,
+    scheduled_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pinned: BTreeSet<String>,
+    #[serde(default)]
+    excluded: BTreeSet<String>,
+}
+
+/// Forwards practice session events to the app as messages.
+struct AppSessionListener {
+    link: Scope<PracticePlannerApp>,
+}
+
+impl fmt::Debug for AppSessionListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AppSessionListener").finish_non_exhaustive()
+    }
+}
+
+impl SessionListener for AppSessionListener {
+    fn on_event(&mut self, event: &SessionEvent) -> Result<()> {
+        self.link.send_message(Msg::SessionEvent(event.clone()));
+        Ok(())
+    }
+}
+
+fn add_session_listeners(scheduler: &mut SchedulePlanner, link: &Scope<PracticePlannerApp>) {
+    // play a ding sound through the audio player as the session moves on
+    scheduler.add_session_listener(Box::new(AlertListener::new(Box::new(
+        EventBusAlertSink::new(),
+    ))));
+    scheduler.add_session_listener(Box::new(AppSessionListener { link: link.clone() }));
+}
+
+fn load_scheduler(profile: &str, link: &Scope<PracticePlannerApp>) -> SchedulePlanner {
+    let config = LocalStorage::get(profile_key(profile, CONFIG_KEY));
+    let history = load_history(profile);
+    let mut scheduler = match config {
+        Ok(conf) => {
+            log::debug!("Found saved data for profile {}: {:#?}", profile, history);
+            SchedulePlanner::from_saved(conf, history.unwrap_or_default())
+        }
+        Err(_e) => {
+            log::debug!("Did not find saved data for profile {}", profile);
+            SchedulePlanner::new()
+        }
+    };
+    // a schedule picked on an earlier day is replaced once it's next updated
+    if let Ok(today) = LocalStorage::get::<SavedToday>(profile_key(profile, TODAY_KEY)) {
+        scheduler.todays_schedule = today.schedule;
+        scheduler.todays_schedule_time = today.scheduled_at;
+        scheduler.todays_pinned = today.pinned;
+        scheduler.todays_excluded = today.excluded;
+    }
+    add_session_listeners(&mut scheduler, link);
+
+    scheduler
 }
 
 // Splitting this out makes local debugging easier
@@ -76,8 +205,8 @@ pub fn get_current_time() -> DateTime<Utc> {
 
 pub struct PracticePlannerApp {
     scheduler: SchedulePlanner,
+    profiles: PracticeProfiles,
     interval: Option<Interval>,
-    event_bus: Dispatcher<EventBus>,
     // TODO consider using yewdux for all this
     active_tab: usize,
     modal_closed: bool,
@@ -92,11 +221,18 @@ pub struct PracticePlannerApp {
     selected_skill: Option<Arc<PracticeSkill>>,
     // TODO this should really be a prop in a Settings (sub)component
     selected_exercise: Option<Arc<PracticeExercise>>,
-    paused: bool,
-    pause_time_elapsed: Duration,
-    pause_time_started: Option<DateTime<Utc>>,
     visible_exercise_md: String,
     rendered_exercise: Html,
+    // the practice session that was just completed, so notes can be added to it
+    last_completed_session: Option<DateTime<Utc>>,
+    notes_query: String,
+    // kept alive until the next export so the download can finish
+    export_url: Option<ObjectUrl>,
+    // kept alive until the uploaded file has been read
+    import_reader: Option<FileReader>,
+    // a session cut short by a reload, until it's resumed or discarded
+    saved_session: Option<SavedSession>,
+    session_saved_at: Option<DateTime<Utc>>,
 }
 
 impl PracticePlannerApp {
@@ -105,7 +241,7 @@ impl PracticePlannerApp {
         (idx, skill): (usize, &Arc<PracticeSkill>),
         active_idx: Option<usize>,
         practicing: bool,
-        _link: &Scope<Self>,
+        link: &Scope<Self>,
     ) -> Html {
         // XXX TODO handle this better?
         let active = active_idx.unwrap_or_default();
@@ -115,6 +251,39 @@ impl PracticePlannerApp {
         } else if practicing && active as usize > idx {
             class.push("completed-skill")
         }
+        let session = self
+            .scheduler
+            .practice_session
+            .as_ref()
+            .filter(|_| practicing);
+        let skipped = practicing
+            && (active as usize) > idx
+            && session.map_or(false, |ps| ps.is_skipped(idx));
+        if skipped {
+            class.push("skipped-skill");
+        }
+        let segment = match session {
+            Some(ps) if ps.is_segment(&skill.skill_name) && idx == 0 => Some("Warm-up: "),
+            Some(ps) if ps.is_segment(&skill.skill_name) => Some("Cool-down: "),
+            _ => None,
+        };
+        // interleaved sessions list each skill once per round
+        let round = match session {
+            Some(ps) if segment.is_none() && ps.rounds() > 1 => {
+                Some(format!(" (round {}/{})", ps.round_of(idx) + 1, ps.rounds()))
+            }
+            _ => None,
+        };
+        // skills that haven't been reached yet can be reordered
+        let movable = session.map_or(0..0, |ps| ps.movable_skills());
+        let move_up = movable.contains(&idx) && idx > movable.start;
+        let move_down = movable.contains(&idx) && idx + 1 < movable.end;
+        let pinned = self.scheduler.is_pinned(&skill.skill_name);
+        let (pin_name, swap_name, exclude_name) = (
+            skill.skill_name.clone(),
+            skill.skill_name.clone(),
+            skill.skill_name.clone(),
+        );
         html! {
             <li {class}>
                 <div class="view">
@@ -124,7 +293,41 @@ impl PracticePlannerApp {
                         checked={practicing && active as usize > idx}
                         disabled=true
                     />
-                    <label>{ &skill.skill_name}</label>
+                    <label>
+                        if let Some(segment) = segment {
+                            { segment }
+                        }
+                        { &skill.skill_name }
+                        if let Some(round) = round {
+                            { round }
+                        }
+                        if skipped {
+                            { " (skipped)" }
+                        }
+                    </label>
+                    if move_up {
+                        <a class="skill-move" title="Practice Earlier" onclick={link.callback(move |_| Msg::MoveSkill(idx, idx - 1))}>
+                            <i class="fas fa-arrow-up"></i>
+                        </a>
+                    }
+                    if move_down {
+                        <a class="skill-move" title="Practice Later" onclick={link.callback(move |_| Msg::MoveSkill(idx, idx + 1))}>
+                            <i class="fas fa-arrow-down"></i>
+                        </a>
+                    }
+                    // before practicing, today's schedule can be edited
+                    if !practicing {
+                        <a class="skill-move" title={if pinned { "Unpin" } else { "Keep When Shuffling" }}
+                           onclick={link.callback(move |_| Msg::PinSkill(pin_name.clone(), !pinned))}>
+                            <i class={if pinned { "fas fa-thumbtack has-text-success" } else { "fas fa-thumbtack" }}></i>
+                        </a>
+                        <a class="skill-move" title="Swap For Another Skill" onclick={link.callback(move |_| Msg::SwapSkill(swap_name.clone()))}>
+                            <i class="fas fa-exchange-alt"></i>
+                        </a>
+                        <a class="skill-move" title="Leave Out Today" onclick={link.callback(move |_| Msg::ExcludeSkill(exclude_name.clone()))}>
+                            <i class="fas fa-times"></i>
+                        </a>
+                    }
                 </div>
                 // { self.view_entry_edit_input((idx, skill), link) }
             </li>
@@ -133,7 +336,7 @@ impl PracticePlannerApp {
 
     fn view_history_list(
         &self,
-        history_list: BTreeMap<Date<Utc>, HashSet<Arc<PracticeSkill>>>,
+        history_list: BTreeMap<Date<Utc>, DaySessions<'_>>,
         _link: &Scope<Self>,
     ) -> Html {
         let _class = Classes::from("todo");
@@ -147,13 +350,20 @@ impl PracticePlannerApp {
         // XXX TODO convert to a table view https://bulma.io/documentation/elements/table/
         let hl = history_list
             .iter()
-            .map(|(day, day_skills)| {
-                let mut dc = day_skills
+            .map(|(day, sessions)| {
+                // a day's sessions are listed apart, by the time each finished
+                let ds = sessions
                     .iter()
-                    .map(|cat| format!("{}", cat))
+                    .map(|(time, record)| {
+                        let dc = record
+                            .skills
+                            .iter()
+                            .map(|cat| format!("{}", cat))
+                            .collect::<Vec<_>>();
+                        html! { <li><em>{ time.format("%H:%M") }</em>{ " " }{ dc.join(", ") }</li> }
+                    })
                     .collect::<Vec<_>>();
-                dc.sort();
-                html! { <li><strong>{ day }</strong>{ dc.join(", ") }</li> }
+                html! { <li><strong>{ day }</strong><ul>{ ds }</ul></li> }
             })
             .collect::<Vec<_>>();
         html! {
@@ -163,14 +373,500 @@ impl PracticePlannerApp {
         }
     }
 
+    fn view_journal_notes(&self, link: &Scope<Self>) -> Html {
+        let notes = self
+            .scheduler
+            .search_notes(&self.notes_query)
+            .iter()
+            .map(|n| {
+                html! {
+                    <li>
+                        <strong>{ n.time.date() }</strong>{ " " }<em>{ &n.skill_name }</em>
+                        <p>{ &n.note }</p>
+                    </li>
+                }
+            })
+            .collect::<Vec<_>>();
+
+        html! {
+            <>
+            <p><label for="notes_search">{"Practice Journal"}</label></p>
+            <input id="notes_search" class="input is-primary" type="text" placeholder="Search notes"
+                   value={self.notes_query.clone()}
+                   oninput={link.callback(|e: InputEvent| Msg::SearchNotes(e.target_unchecked_into::<HtmlInputElement>().value()))} />
+            {
+                if notes.is_empty() {
+                    html! { <strong>{ "No notes" }</strong> }
+                } else {
+                    html! { <ul class="notes-list">{ notes }</ul> }
+                }
+            }
+            </>
+        }
+    }
+
+    /// Form for recording practice done away from the app.
+    fn view_log_practice(&self, link: &Scope<Self>) -> Html {
+        let skills = self
+            .scheduler
+            .config
+            .skills
+            .iter()
+            .enumerate()
+            .map(|(idx, skill)| {
+                html! {
+                    <li>
+                        <input id={format!("log_minutes_{}", idx)} class="input" type="number" min="0" placeholder="0" />
+                        { " " }{ &skill.skill_name }
+                    </li>
+                }
+            })
+            .collect::<Vec<_>>();
+
+        html! {
+            <>
+            <p><label for="log_date">{"Log Practice"}</label></p>
+            <p>{"Record practice done away from the app, such as at a lesson. Enter the minutes spent on each skill practiced."}</p>
+            <input id="log_date" class="input is-primary" type="date"
+                   max={get_current_time().format("%Y-%m-%d").to_string()} />
+            <ul class="log-list">{ skills }</ul>
+            <button class="favorite styled"
+                    type="button"
+                    onclick={link.callback(|_| Msg::LogPractice)}
+                    >
+                    {"Log Practice"}
+            </button>
+            </>
+        }
+    }
+
+    /// Notes box for the skill currently being practiced.
+    fn view_session_notes(&self, link: &Scope<Self>) -> Html {
+        let ps = match &self.scheduler.practice_session {
+            Some(ps) => ps,
+            None => return html! {<></>},
+        };
+        let note = ps.get_note(&ps.current_skill).cloned().unwrap_or_default();
+
+        html! {
+            <div class="skill-notes">
+                <label for="skill_notes">{ format!("Notes on {}", ps.current_skill.skill_name) }</label>
+                <textarea id="skill_notes" class="textarea" placeholder="e.g. left hand tension at 120bpm"
+                          value={note}
+                          oninput={link.callback(|e: InputEvent| Msg::UpdateSessionNote(e.target_unchecked_into::<HtmlTextAreaElement>().value()))}>
+                </textarea>
+            </div>
+        }
+    }
+
+    /// Notes boxes for each skill of the practice session that was just completed.
+    fn view_completed_session_notes(&self, link: &Scope<Self>) -> Html {
+        let (time, record) = match self
+            .last_completed_session
+            .and_then(|t| self.scheduler.history.get(&t).map(|r| (t, r)))
+        {
+            Some(r) => r,
+            None => return html! {<></>},
+        };
+
+        html! {
+            <div class="skill-notes">
+                <p><strong>{"Session complete!"}</strong>{" Add any notes on today's practice."}</p>
+                { for record.skills.iter().map(|skill| {
+                    let skill_name = skill.skill_name.clone();
+                    html! {
+                        <>
+                        <label>
+                            { skill.skill_name.clone() }
+                            if record.skipped.contains(&skill.skill_name) {
+                                { " (skipped)" }
+                            }
+                        </label>
+                        <textarea class="textarea"
+                                  value={record.notes.get(&skill.skill_name).cloned().unwrap_or_default()}
+                                  oninput={link.callback(move |e: InputEvent| Msg::UpdateHistoryNote(time, skill_name.clone(), e.target_unchecked_into::<HtmlTextAreaElement>().value()))}>
+                        </textarea>
+                        </>
+                    }
+                })}
+            </div>
+        }
+    }
+
+    fn view_data_transfer(&self, link: &Scope<Self>) -> Html {
+        html! {
+            <>
+            <p><label for="import_file">{"Move Data Between Devices"}</label></p>
+            <p>{"Download this profile's settings and history to import them into another browser or ppcli. Importing replaces the settings and adds the file's history to this profile."}</p>
+            <div class="field is-grouped">
+                <div class="control">
+                    <button class="favorite styled"
+                            type="button"
+                            onclick={link.callback(|_| Msg::ExportData)}
+                            >
+                            {"Download Data"}
+                    </button>
+                </div>
+                <div class="control">
+                    <input id="import_file"
+                           type="file"
+                           accept=".json,application/json"
+                           disabled={self.scheduler.practicing}
+                           onchange={link.callback(|e: Event| {
+                               let input = e.target_unchecked_into::<HtmlInputElement>();
+                               Msg::ImportFile(input.files().and_then(|files| files.get(0)))
+                           })} />
+                </div>
+            </div>
+            </>
+        }
+    }
+
+    fn view_trash(&self, link: &Scope<Self>) -> Html {
+        let config = &self.scheduler.config;
+        if config.archived_skills.is_empty() && config.archived_exercises.is_empty() {
+            return html! {
+                <strong>{ "The trash is empty" }</strong>
+            };
+        }
+
+        let skills = config
+            .archived_skills
+            .iter()
+            .map(|skill| {
+                let restore_name = skill.skill_name.clone();
+                let purge_name = skill.skill_name.clone();
+                html! {
+                    <li>
+                        { skill.skill_name.clone() }
+                        <a title="Restore Skill" onclick={link.callback(move |_| Msg::RestoreSkill(restore_name.clone()))}>
+                            <span class="icon is-medium has-text-success">
+                                <i class="fas fa-trash-restore fa-lg"></i>
+                            </span>
+                        </a>
+                        <a title="Delete Skill" onclick={link.callback(move |_| Msg::ShowPurgeSkillPrompt(purge_name.clone()))}>
+                            <span class="icon is-medium has-text-danger">
+                                <i class="fas fa-trash fa-lg"></i>
+                            </span>
+                        </a>
+                    </li>
+                }
+            })
+            .collect::<Vec<_>>();
+        let exercises = config
+            .archived_exercises
+            .iter()
+            .enumerate()
+            .map(|(idx, archived)| {
+                html! {
+                    <li>
+                        { format!("{}: {}", archived.skill_name, archived.exercise.exercise_name) }
+                        <a title="Restore Exercise" onclick={link.callback(move |_| Msg::RestoreExercise(idx))}>
+                            <span class="icon is-medium has-text-success">
+                                <i class="fas fa-trash-restore fa-lg"></i>
+                            </span>
+                        </a>
+                        <a title="Delete Exercise" onclick={link.callback(move |_| Msg::ShowPurgeExercisePrompt(idx))}>
+                            <span class="icon is-medium has-text-danger">
+                                <i class="fas fa-trash fa-lg"></i>
+                            </span>
+                        </a>
+                    </li>
+                }
+            })
+            .collect::<Vec<_>>();
+
+        html! {
+            <>
+            <p><strong>{"Skills"}</strong></p>
+            <ul class="trash-list">{ skills }</ul>
+            <p><strong>{"Exercises"}</strong></p>
+            <ul class="trash-list">{ exercises }</ul>
+            </>
+        }
+    }
+
     fn save(&self) -> Result<()> {
         // TODO need to bubble this error up actually
         log::debug!("Saving...");
-        LocalStorage::set(CONFIG_KEY, &self.scheduler.config).expect("able to save");
-        LocalStorage::set(HISTORY_KEY, &self.scheduler.history).expect("able to save");
+        let profile = &self.profiles.active_profile;
+        LocalStorage::set(profile_key(profile, CONFIG_KEY), &self.scheduler.config)
+            .expect("able to save");
+        LocalStorage::set(profile_key(profile, HISTORY_KEY), &self.scheduler.history)
+            .expect("able to save");
+        let today = SavedToday {
+            schedule: self.scheduler.todays_schedule.clone(),
+            scheduled_at: self.scheduler.todays_schedule_time,
+            pinned: self.scheduler.todays_pinned.clone(),
+            excluded: self.scheduler.todays_excluded.clone(),
+        };
+        LocalStorage::set(profile_key(profile, TODAY_KEY), &today).expect("able to save");
+        LocalStorage::set(PROFILES_KEY, &self.profiles).expect("able to save");
         Ok(())
     }
 
+    /// Runs the practice timer's ticks, unless they're already running.
+    fn start_ticking(&mut self, ctx: &Context<Self>) {
+        if self.interval.is_some() {
+            return;
+        }
+
+        let link = ctx.link().clone();
+        self.interval = Some(Interval::new(500, move || {
+            link.send_message(Msg::PracticeTick)
+        }));
+    }
+
+    /// Wraps up after the last skill of a practice session is completed.
+    fn finish_practice(&mut self, now: DateTime<Utc>) {
+        if let Some(timer) = self.interval.take() {
+            drop(timer);
+        }
+        self.last_completed_session = Some(now);
+        self.save().expect("unable to save");
+        self.scheduler
+            .update_todays_schedule(false, now)
+            .expect("unable to update schedule");
+    }
+
+    /// Renders the practice session's current exercise, if any.
+    fn show_current_exercise(&mut self) {
+        self.visible_exercise_md = match &self.scheduler.practice_session {
+            Some(ps) => match &ps.current_exercise {
+                Some(ce) => ce.exercise_markdown_contents.clone(),
+                None => "".to_string(),
+            },
+            None => "".to_string(),
+        };
+        self.rendered_exercise = render_exercise(self.visible_exercise_md.clone());
+    }
+
+    /// Saves the practice session underway, so it survives a reload, or
+    /// removes the saved one once there's no session. A session waiting to
+    /// be resumed is left alone.
+    fn save_session(&mut self, now: DateTime<Utc>) {
+        if self.saved_session.is_some() {
+            return;
+        }
+
+        let key = profile_key(&self.profiles.active_profile, SESSION_KEY);
+        match self.scheduler.save_session(now) {
+            Some(saved) => {
+                if let Err(e) = LocalStorage::set(key, &saved) {
+                    log::warn!("Unable to save the practice session: {}", e);
+                }
+                self.session_saved_at = Some(now);
+            }
+            None => {
+                LocalStorage::delete(key);
+                self.session_saved_at = None;
+            }
+        }
+    }
+
+    /// Saves a running session if it hasn't been for a while.
+    fn save_session_if_due(&mut self, now: DateTime<Utc>) {
+        let due = match self.session_saved_at {
+            Some(saved_at) => now - saved_at >= Duration::seconds(SESSION_SAVE_SECONDS),
+            None => true,
+        };
+        if due {
+            self.save_session(now);
+        }
+    }
+
+    /// Offers to resume the active profile's session that was cut short, if
+    /// there is one.
+    fn offer_saved_session(&mut self, link: &Scope<Self>) {
+        let key = profile_key(&self.profiles.active_profile, SESSION_KEY);
+        let saved: SavedSession = match LocalStorage::get(&key) {
+            Ok(saved) => saved,
+            Err(_) => {
+                self.saved_session = None;
+                return;
+            }
+        };
+
+        let started_minutes = (get_current_time() - saved.start_time()).num_minutes();
+        self.displaying_modal = true;
+        self.modal_closed = false;
+        self.modal_title = "Info".to_string();
+        self.modal_type = "info".to_string();
+        self.modal_content = html! {
+            <div>
+            <h1>{"Resume practicing?"}</h1>
+            <p>{ format!("A practice session started {} minutes ago stopped during ", started_minutes) }<strong>{ saved.current_skill_name().to_string() }</strong>{"."}</p>
+            <p>{"Resuming picks it back up where it stopped, paused. Discarding it leaves it out of your history."}</p>
+            <div>
+                <button class="favorite styled"
+                        type="button"
+                        onclick={link.callback(|_| Msg::ResumeSavedSession)}>
+                    {"Resume"}
+                </button>
+                <button class="favorite styled"
+                        type="button"
+                        onclick={link.callback(|_| Msg::DiscardSavedSession)}>
+                    {"Discard"}
+                </button>
+            </div>
+            </div>
+        };
+        self.saved_session = Some(saved);
+    }
+
+    fn show_error(&mut self, err: impl std::fmt::Display) {
+        self.displaying_modal = true;
+        self.modal_closed = false;
+        self.modal_title = "Danger".to_string();
+        self.modal_type = "danger".to_string();
+        self.modal_content = html! {
+            <div>
+            <p>{ err.to_string() }</p>
+            </div>
+        };
+    }
+
+    fn view_profile_settings(&self, link: &Scope<Self>) -> Html {
+        let profile_options = self
+            .profiles
+            .profiles
+            .iter()
+            .map(|name| {
+                html! {
+                    <option value={name.clone()} selected={*name == self.profiles.active_profile}>
+                        { name.clone() }
+                    </option>
+                }
+            })
+            .collect::<Vec<_>>();
+
+        html! {
+            <>
+            <p><label for="profile_list">{"Profile"}</label></p>
+            <div class="select">
+            <select id="profile_list"
+                    disabled={self.scheduler.practicing}
+                    onchange={link.callback(|e: Event| Msg::SwitchProfile(e.target_unchecked_into::<HtmlSelectElement>().value()))}>
+                { profile_options }
+            </select>
+            </div>
+            {
+                if !self.profiles.is_default() {
+                    html! {
+                        <div class="icon-text">
+                            <a title="Delete Profile" onclick={link.callback(|_| Msg::ShowDeleteProfilePrompt)}>
+                                <span class="icon is-medium has-text-success">
+                                    <i class="fas fa-trash fa-lg"></i>
+                                </span>
+                            </a>
+                        </div>
+                    }
+                } else {
+                    html! {<></>}
+                }
+            }
+            <div class="field has-addons">
+                <div class="control">
+                    <input id="new_profile_name" class="input is-primary" type="text" placeholder="New profile name" />
+                </div>
+                <div class="control">
+                    <button class="favorite styled"
+                            type="button"
+                            onclick={link.callback(|_| Msg::AddProfile)}
+                            >
+                            {"Add Profile"}
+                    </button>
+                </div>
+            </div>
+            </>
+        }
+    }
+
+    /// Picker for adding a skill to today's schedule by hand.
+    fn view_add_skill_today(&self, link: &Scope<Self>) -> Html {
+        let schedule = self.scheduler.get_todays_schedule();
+        let skill_options = self
+            .scheduler
+            .scheduled_skills()
+            .filter(|skill| {
+                !schedule.is_some_and(|schedule| {
+                    schedule.iter().any(|s| s.skill_name == skill.skill_name)
+                })
+            })
+            .map(|skill| {
+                html! {
+                    <option value={skill.skill_name.clone()}>{ skill.skill_name.clone() }</option>
+                }
+            })
+            .collect::<Vec<_>>();
+        if skill_options.is_empty() {
+            return html! {};
+        }
+
+        html! {
+            <div class="select">
+            <select id="add_skill_today"
+                    onchange={link.callback(|e: Event| Msg::AddSkillToday(e.target_unchecked_into::<HtmlSelectElement>().value()))}>
+                <option value="" selected=true>{"Add a skill for today"}</option>
+                { skill_options }
+            </select>
+            </div>
+        }
+    }
+
+    fn view_segment_settings(&self, kind: SegmentKind, label: &str) -> Html {
+        let segment = self.scheduler.segment(kind);
+        let selected_skill = segment.map(|s| s.skill_name.clone()).unwrap_or_default();
+        let skill_options = self
+            .scheduler
+            .config
+            .skills
+            .iter()
+            .map(|skill| {
+                html! {
+                    <option value={skill.skill_name.clone()} selected={skill.skill_name == selected_skill}>
+                        { skill.skill_name.clone() }
+                    </option>
+                }
+            })
+            .collect::<Vec<_>>();
+        let minutes = segment.map_or(5, |s| s.practice_time.num_minutes());
+
+        html! {
+            <>
+            <p><label for={format!("{}_skill", kind.name())}>{label}</label></p>
+            <div class="field has-addons">
+                <div class="control">
+                    <div class="select">
+                    <select id={format!("{}_skill", kind.name())}>
+                        <option value="" selected={segment.is_none()}>{"None"}</option>
+                        { skill_options }
+                    </select>
+                    </div>
+                </div>
+                <div class="control">
+                    <input id={format!("{}_minutes", kind.name())} class="input is-primary" type="text" placeholder="Minutes" value={format!("{}", minutes)} />
+                </div>
+            </div>
+            </>
+        }
+    }
+
+    fn view_timer(&self) -> String {
+        let session = self.scheduler.practice_session.as_ref().unwrap();
+        if session.is_resting() {
+            format!(
+                "Rest break: {}, then {}",
+                session.time_left.hhmmss(),
+                session.current_skill
+            )
+        } else if session.time_over > Duration::seconds(0) {
+            format!("Overtime: +{}", session.time_over.hhmmss())
+        } else {
+            format!("Time left: {}", session.time_left.hhmmss())
+        }
+    }
+
     fn view_practice_tab(
         &self,
         _practice_session: &Option<PracticeSession>,
@@ -189,13 +885,18 @@ impl PracticePlannerApp {
                             <>
                             <div class="level-item">
                                 <div class="column">
-                                    <div><strong>{ "Time left: " }{ self.scheduler.practice_session.as_ref().unwrap().time_left.hhmmss() }</strong></div>
+                                    <div><strong>{ self.view_timer() }</strong></div>
                                     <div>
                                         <button class="favorite styled"
                                                 type="button"
+                                                disabled={self.scheduler.practice_session.as_ref().unwrap().is_resting()}
                                                 onclick={link.callback(|_| Msg::PausePracticing)}
                                                 >
-                                                {"Pause Practicing"}
+                                                { if self.scheduler.practice_session.as_ref().unwrap().is_paused() {
+                                                    "Resume Practicing"
+                                                } else {
+                                                    "Pause Practicing"
+                                                } }
                                         </button>
                                         <button class="favorite styled"
                                                 type="button"
@@ -204,6 +905,34 @@ impl PracticePlannerApp {
                                                 {"Stop Practicing"}
                                         </button>
                                     </div>
+                                    <div>
+                                        <button class="favorite styled"
+                                                type="button"
+                                                disabled={self.scheduler.practice_session.as_ref().unwrap().get_current_skill_idx() == 0}
+                                                onclick={link.callback(|_| Msg::PreviousSkill)}
+                                                >
+                                                {"Previous Skill"}
+                                        </button>
+                                        <button class="favorite styled"
+                                                type="button"
+                                                onclick={link.callback(|_| Msg::SkipSkill)}
+                                                >
+                                                { if self.scheduler.practice_session.as_ref().unwrap().is_resting() {
+                                                    "End Break"
+                                                } else if self.scheduler.practice_session.as_ref().unwrap().time_left > Duration::seconds(0) {
+                                                    "Skip Skill"
+                                                } else {
+                                                    "Next Skill"
+                                                } }
+                                        </button>
+                                        <button class="favorite styled"
+                                                type="button"
+                                                disabled={self.scheduler.practice_session.as_ref().unwrap().is_resting()}
+                                                onclick={link.callback(|_| Msg::ExtendSkill)}
+                                                >
+                                                {format!("+{} Minutes", EXTEND_MINUTES)}
+                                        </button>
+                                    </div>
                                 </div>
                             </div>
                             </>
@@ -229,6 +958,14 @@ impl PracticePlannerApp {
                                     </a>
                                 </div>
                             </div>
+                            { match self.scheduler.todays_sessions(get_current_time()).count() {
+                                0 => html! {},
+                                completed => html! {
+                                    <div class="level-item">
+                                        <span>{ format!("Session {} today", completed + 1) }</span>
+                                    </div>
+                                },
+                            } }
                             </>
                         }
                     }}
@@ -251,18 +988,20 @@ impl PracticePlannerApp {
             None => None,
         };
         let practicing = self.scheduler.practicing;
+        // while practicing, the list includes the warm-up and cool-down
+        let schedule = match practice_session {
+            Some(ps) if practicing => Some(&ps.schedule),
+            _ => self.scheduler.get_todays_schedule(),
+        };
 
         html! {
             <>
             <ul class="skill-list">
-            {
-                if self.scheduler.get_todays_schedule().is_some() {
-                    html! { for self.scheduler.get_todays_schedule().unwrap().iter().enumerate().map(|e| self.view_skill(e, active, practicing, link)) }
-                } else {
-                    html! {}
-                }
-            }
+            { for schedule.into_iter().flatten().enumerate().map(|e| self.view_skill(e, active, practicing, link)) }
             </ul>
+            if !practicing {
+                { self.view_add_skill_today(link) }
+            }
             </>
         }
     }
@@ -272,25 +1011,10 @@ impl Component for PracticePlannerApp {
     type Message = Msg;
     type Properties = TabDisplayProps;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        let config = LocalStorage::get(CONFIG_KEY);
-        let history = LocalStorage::get(HISTORY_KEY);
-        let mut scheduler = match config {
-            Ok(conf) => {
-                log::debug!("Found saved data: {:#?}", history);
-                SchedulePlanner {
-                    config: conf,
-                    history: history.unwrap_or_default(),
-                    todays_schedule: None,
-                    practicing: false,
-                    practice_session: None,
-                }
-            }
-            Err(_e) => {
-                log::debug!("Did not find saved data");
-                SchedulePlanner::new()
-            }
-        };
+    fn create(ctx: &Context<Self>) -> Self {
+        let profiles: PracticeProfiles =
+            LocalStorage::get(PROFILES_KEY).unwrap_or_else(|_| PracticeProfiles::new());
+        let mut scheduler = load_scheduler(&profiles.active_profile, ctx.link());
 
         let practice_minutes = scheduler.config.skill_practice_time.num_minutes() as usize;
 
@@ -304,14 +1028,13 @@ impl Component for PracticePlannerApp {
             true
         });
         // Display the info modal on first page load
-        if first_page_view {
+        let mut app = if first_page_view {
             Self {
                 scheduler,
+                profiles,
                 interval: None,
-                event_bus: EventBus::dispatcher(),
                 active_tab: 0,
                 modal_closed: false,
-                paused: false,
                 displaying_modal: true,
                 modal_content: html! {
                     <div>
@@ -335,20 +1058,22 @@ impl Component for PracticePlannerApp {
                 practice_minutes,
                 selected_skill: None,
                 selected_exercise: None,
-                pause_time_elapsed: Duration::seconds(0),
-                pause_time_started: None,
                 visible_exercise_md: "".to_string(),
                 rendered_exercise: html! {},
+                last_completed_session: None,
+                notes_query: "".to_string(),
+                export_url: None,
+                import_reader: None,
+                saved_session: None,
+                session_saved_at: None,
             }
         } else {
             Self {
                 scheduler,
+                profiles,
                 interval: None,
-                event_bus: EventBus::dispatcher(),
                 active_tab: 0,
-                pause_time_elapsed: Duration::seconds(0),
                 modal_closed: false,
-                paused: false,
                 displaying_modal: false,
                 modal_content: html! {},
                 modal_title: "Danger".to_string(),
@@ -357,11 +1082,19 @@ impl Component for PracticePlannerApp {
                 practice_minutes,
                 selected_skill: None,
                 selected_exercise: None,
-                pause_time_started: None,
                 visible_exercise_md: "".to_string(),
                 rendered_exercise: html! {},
+                last_completed_session: None,
+                notes_query: "".to_string(),
+                export_url: None,
+                import_reader: None,
+                saved_session: None,
+                session_saved_at: None,
             }
-        }
+        };
+        app.offer_saved_session(ctx.link());
+
+        app
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -369,38 +1102,19 @@ impl Component for PracticePlannerApp {
             // TODO: lots of code duplication in here
             Msg::NextExercise => {
                 self.scheduler
-                    .practice_session
-                    .as_mut()
-                    .unwrap()
-                    .next_exercise();
-                let visible_exercise_md = match &self.scheduler.practice_session {
-                    Some(ps) => match &ps.current_exercise {
-                        Some(ce) => ce.exercise_markdown_contents.clone(),
-                        None => "".to_string(),
-                    },
-                    None => "".to_string(),
-                };
-                self.visible_exercise_md = visible_exercise_md;
-                let rendered_exercise = render_exercise(self.visible_exercise_md.clone());
-                self.rendered_exercise = rendered_exercise;
+                    .next_exercise(get_current_time())
+                    .expect("unable to change exercise");
             }
             Msg::PreviousExercise => {
                 self.scheduler
-                    .practice_session
-                    .as_mut()
-                    .unwrap()
-                    .previous_exercise();
-                let visible_exercise_md = match &self.scheduler.practice_session {
-                    Some(ps) => match &ps.current_exercise {
-                        Some(ce) => ce.exercise_markdown_contents.clone(),
-                        None => "".to_string(),
-                    },
-                    None => "".to_string(),
-                };
-                self.visible_exercise_md = visible_exercise_md;
-                let rendered_exercise = render_exercise(self.visible_exercise_md.clone());
-                self.rendered_exercise = rendered_exercise;
+                    .previous_exercise(get_current_time())
+                    .expect("unable to change exercise");
             }
+            Msg::SessionEvent(event) => match event {
+                SessionEvent::ExerciseChanged { .. } => self.show_current_exercise(),
+                // the timer's display is updated on every tick
+                _ => return false,
+            },
             Msg::SaveSettings => {
                 // TODO: there has to be a better way to handle forms than this, but it was easy for now
                 // fetch form values
@@ -414,6 +1128,14 @@ impl Component for PracticePlannerApp {
                     .get_element_by_id("skill_count")
                     .expect("should have a skill count element")
                     .unchecked_into::<HtmlInputElement>();
+                let overtime_el = document
+                    .get_element_by_id("overtime")
+                    .expect("should have an overtime element")
+                    .unchecked_into::<HtmlInputElement>();
+                let exclude_practiced_today_el = document
+                    .get_element_by_id("exclude_practiced_today")
+                    .expect("should have an exclude practiced today element")
+                    .unchecked_into::<HtmlInputElement>();
                 let skill_minutes = skill_minutes_el.value();
                 let skill_count = skill_count_el.value();
 
@@ -424,6 +1146,95 @@ impl Component for PracticePlannerApp {
                 // set on self
                 self.scheduler.config.skill_practice_time = Duration::minutes(skill_minutes);
                 self.scheduler.config.skills_per_day = skill_count;
+                self.scheduler.config.overtime = overtime_el.checked();
+                self.scheduler.config.exclude_practiced_today =
+                    exclude_practiced_today_el.checked();
+                for kind in [SegmentKind::WarmUp, SegmentKind::CoolDown] {
+                    let skill_name = document
+                        .get_element_by_id(&format!("{}_skill", kind.name()))
+                        .expect("should have a segment skill element")
+                        .unchecked_into::<HtmlSelectElement>()
+                        .value();
+                    let minutes = document
+                        .get_element_by_id(&format!("{}_minutes", kind.name()))
+                        .expect("should have a segment minutes element")
+                        .unchecked_into::<HtmlInputElement>()
+                        .value();
+                    let segment = match (skill_name.is_empty(), minutes.trim().parse::<i64>()) {
+                        (true, _) => None,
+                        (false, Ok(minutes)) => Some(PracticeSegment {
+                            skill_name,
+                            practice_time: Duration::minutes(minutes),
+                        }),
+                        (false, Err(_)) => {
+                            self.show_error(format!(
+                                "The {} minutes must be a number",
+                                kind.name()
+                            ));
+                            return true;
+                        }
+                    };
+                    if let Err(e) = self.scheduler.set_segment(kind, segment) {
+                        self.show_error(e);
+                        return true;
+                    }
+                }
+                let rest_minutes = document
+                    .get_element_by_id("rest_minutes")
+                    .expect("should have a rest minutes element")
+                    .unchecked_into::<HtmlInputElement>()
+                    .value();
+                let rest_every = document
+                    .get_element_by_id("rest_every")
+                    .expect("should have a rest every element")
+                    .unchecked_into::<HtmlInputElement>()
+                    .value();
+                let rest_breaks = match (
+                    rest_minutes.trim().parse::<i64>(),
+                    rest_every.trim().parse::<usize>(),
+                ) {
+                    (Ok(0), _) => None,
+                    (Ok(minutes), Ok(every)) => Some(RestBreaks {
+                        every_skills: every,
+                        rest_time: Duration::minutes(minutes),
+                    }),
+                    _ => {
+                        self.show_error("The rest break minutes and skills must be numbers");
+                        return true;
+                    }
+                };
+                if let Err(e) = self.scheduler.set_rest_breaks(rest_breaks) {
+                    self.show_error(e);
+                    return true;
+                }
+                let interleave_rounds = document
+                    .get_element_by_id("interleave_rounds")
+                    .expect("should have an interleave rounds element")
+                    .unchecked_into::<HtmlInputElement>()
+                    .value();
+                let interleave_minutes = document
+                    .get_element_by_id("interleave_minutes")
+                    .expect("should have an interleave minutes element")
+                    .unchecked_into::<HtmlInputElement>()
+                    .value();
+                let interleaving = match (
+                    interleave_rounds.trim().parse::<usize>(),
+                    interleave_minutes.trim().parse::<i64>(),
+                ) {
+                    (Ok(0), _) => None,
+                    (Ok(rounds), Ok(minutes)) => Some(Interleaving {
+                        rounds,
+                        block_time: Duration::minutes(minutes),
+                    }),
+                    _ => {
+                        self.show_error("The interleaved rounds and minutes must be numbers");
+                        return true;
+                    }
+                };
+                if let Err(e) = self.scheduler.set_interleaving(interleaving) {
+                    self.show_error(e);
+                    return true;
+                }
 
                 // persist to localstorage
                 self.save().expect("able to save");
@@ -457,6 +1268,7 @@ impl Component for PracticePlannerApp {
             Msg::ResetSettings => {
                 // vv full reset
                 self.scheduler = SchedulePlanner::new();
+                add_session_listeners(&mut self.scheduler, ctx.link());
                 let current_time = get_current_time();
                 self.scheduler
                     .update_todays_schedule(false, current_time)
@@ -509,131 +1321,155 @@ impl Component for PracticePlannerApp {
                     self.scheduler
                         .update_todays_schedule(true, current_time)
                         .expect("able to update schedule");
+                    self.save().expect("unable to save");
                 }
             }
             Msg::PausePracticing => {
-                self.paused = !self.paused;
-                if !self.paused {
-                    let now = get_current_time();
-                    // pause time elapsed is a cumulative amount of time spent paused that has elapsed since the category started
-                    self.pause_time_elapsed = self
-                        .pause_time_elapsed
-                        .add(now - self.pause_time_started.unwrap());
-                    self.pause_time_started = None;
+                let now = get_current_time();
+                let paused = match &self.scheduler.practice_session {
+                    // a rest break can't be paused
+                    Some(ps) if ps.is_resting() => return false,
+                    Some(ps) => ps.is_paused(),
+                    None => return false,
+                };
+                if paused {
+                    self.scheduler
+                        .resume_practice_session(now)
+                        .expect("unable to resume");
                     let handle = {
                         let link = ctx.link().clone();
                         Interval::new(100, move || link.send_message(Msg::PracticeTick))
                     };
                     self.interval = Some(handle);
+                    self.save_session(now);
                     return true;
                 }
-                let now = get_current_time();
-                self.pause_time_started = Some(now);
-                if let Some(timer) = self.interval.take() {
-                    drop(timer);
+                self.scheduler
+                    .pause_practice_session(now)
+                    .expect("unable to pause");
+                if let Some(timer) = self.interval.take() {
+                    drop(timer);
+                }
+            }
+            Msg::StopPracticing => {
+                let current_time = get_current_time();
+                self.scheduler
+                    .update_todays_schedule(false, current_time)
+                    .expect("able to update schedule");
+                // save state
+                self.save().expect("unable to save");
+                self.scheduler
+                    .stop_practicing()
+                    .expect("failed to stop practicing");
+                if let Some(timer) = self.interval.take() {
+                    drop(timer);
+                }
+                self.show_current_exercise();
+            }
+            Msg::PracticeTick => {
+                let now = get_current_time();
+                let position = |scheduler: &SchedulePlanner| {
+                    scheduler
+                        .practice_session
+                        .as_ref()
+                        .map(|ps| (ps.state(), ps.get_current_skill_idx()))
+                };
+                let previous_position = position(&self.scheduler);
+                // moves to the next skill once the current one's time runs out
+                let state = self
+                    .scheduler
+                    .tick_practice_session(now)
+                    .expect("unable to advance");
+
+                if state == SessionState::Finished {
+                    self.finish_practice(now);
+                }
+                // saving on every tick would mostly save the same session
+                if position(&self.scheduler) == previous_position {
+                    self.save_session_if_due(now);
+                } else {
+                    self.save_session(now);
+                }
+                return true;
+            }
+            Msg::SkipSkill => {
+                let now = get_current_time();
+                self.scheduler.skip_skill(now).expect("unable to skip");
+                if !self.scheduler.practicing {
+                    self.finish_practice(now);
+                } else {
+                    // skipping while paused starts the next skill's timer
+                    self.start_ticking(ctx);
+                }
+            }
+            Msg::PreviousSkill => {
+                if let Err(e) = self.scheduler.go_back_skill(get_current_time()) {
+                    self.show_error(e);
+                    return true;
+                }
+                self.start_ticking(ctx);
+            }
+            Msg::ExtendSkill => {
+                let extra = Duration::minutes(EXTEND_MINUTES);
+                if let Err(e) = self.scheduler.extend_skill(extra, get_current_time()) {
+                    self.show_error(e);
+                }
+            }
+            Msg::MoveSkill(from, to) => {
+                if let Err(e) = self.scheduler.move_remaining_skill(from, to) {
+                    self.show_error(e);
                 }
             }
-            Msg::StopPracticing => {
-                let current_time = get_current_time();
-                self.scheduler
-                    .update_todays_schedule(false, current_time)
-                    .expect("able to update schedule");
-                // save state
+            Msg::PinSkill(skill_name, pinned) => {
+                if let Err(e) = self.scheduler.pin_skill(&skill_name, pinned) {
+                    self.show_error(e);
+                    return true;
+                }
                 self.save().expect("unable to save");
-                self.scheduler
-                    .stop_practicing()
-                    .expect("failed to stop practicing");
-                if let Some(timer) = self.interval.take() {
-                    drop(timer);
+            }
+            Msg::SwapSkill(skill_name) => {
+                if let Err(e) = self.scheduler.swap_skill(&skill_name, get_current_time()) {
+                    self.show_error(e);
+                    return true;
                 }
-
-                // Reset pause
-                self.pause_time_elapsed = Duration::seconds(0);
-                self.pause_time_started = None;
-                self.paused = false;
-                let visible_exercise_md = match &self.scheduler.practice_session {
-                    Some(ps) => match &ps.current_exercise {
-                        Some(ce) => ce.exercise_markdown_contents.clone(),
-                        None => "".to_string(),
-                    },
-                    None => "".to_string(),
-                };
-                self.visible_exercise_md = visible_exercise_md;
-                let rendered_exercise = render_exercise(self.visible_exercise_md.clone());
-                self.rendered_exercise = rendered_exercise;
+                self.save().expect("unable to save");
             }
-            Msg::PracticeTick => {
-                let now = get_current_time();
-                let time_elapsed = now
-                    .sub(
-                        self.scheduler
-                            .practice_session
-                            .as_ref()
-                            .unwrap()
-                            .skill_start_time,
-                    )
-                    .sub(self.pause_time_elapsed);
-                let total_time = self.scheduler.config.skill_practice_time;
-
-                if time_elapsed > total_time {
-                    // move to next skill
-                    self.scheduler
-                        .advance_practice_session(now)
-                        .expect("unable to advance");
-
-                    // play a ding sound
-                    // TODO this seems overcomplicated maybe just use a callback
-                    self.event_bus
-                        .send(Request::EventBusMsg("ding.wav".to_owned()));
-
-                    if !self.scheduler.practicing {
-                        if let Some(timer) = self.interval.take() {
-                            drop(timer);
-                        }
-                        self.save().expect("unable to save");
-                        self.scheduler
-                            .update_todays_schedule(false, now)
-                            .expect("unable to update schedule");
-                    }
-
-                    self.pause_time_elapsed = Duration::seconds(0);
+            Msg::ExcludeSkill(skill_name) => {
+                if let Err(e) = self
+                    .scheduler
+                    .exclude_skill(&skill_name, get_current_time())
+                {
+                    self.show_error(e);
+                    return true;
                 }
-                let time_left = total_time - time_elapsed;
-                self.scheduler
-                    .practice_session
-                    .as_mut()
-                    .unwrap()
-                    .set_time_left(time_left);
+                self.save().expect("unable to save");
+            }
+            Msg::AddSkillToday(skill_name) => {
+                if skill_name.is_empty() {
+                    return false;
+                }
+                if let Err(e) = self.scheduler.add_skill_today(&skill_name) {
+                    self.show_error(e);
+                    return true;
+                }
+                self.save().expect("unable to save");
             }
             Msg::StartPracticing => {
+                // starting over leaves behind a session that was cut short
+                if self.saved_session.take().is_some() {
+                    LocalStorage::delete(profile_key(&self.profiles.active_profile, SESSION_KEY));
+                }
+                self.last_completed_session = None;
                 let current_time = get_current_time();
-                self.scheduler
-                    .start_daily_practice(current_time)
-                    .expect("failed daily practice");
-                self.scheduler.practice_session.as_mut().unwrap().start_time = current_time;
-                self.scheduler
-                    .practice_session
-                    .as_mut()
-                    .unwrap()
-                    .skill_start_time = current_time;
-                self.scheduler.practice_session.as_mut().unwrap().time_left =
-                    self.scheduler.config.skill_practice_time;
+                if let Err(e) = self.scheduler.start_daily_practice(current_time) {
+                    self.show_error(e);
+                    return true;
+                }
                 let handle = {
                     let link = ctx.link().clone();
                     Interval::new(500, move || link.send_message(Msg::PracticeTick))
                 };
                 self.interval = Some(handle);
-
-                let visible_exercise_md = match &self.scheduler.practice_session {
-                    Some(ps) => match &ps.current_exercise {
-                        Some(ce) => ce.exercise_markdown_contents.clone(),
-                        None => "".to_string(),
-                    },
-                    None => "".to_string(),
-                };
-                self.visible_exercise_md = visible_exercise_md;
-                let rendered_exercise = render_exercise(self.visible_exercise_md.clone());
-                self.rendered_exercise = rendered_exercise;
             }
             Msg::ChangeTab(i) => {
                 self.active_tab = i;
@@ -714,52 +1550,440 @@ impl Component for PracticePlannerApp {
                 self.selected_exercise = Some(selected_skill.exercises[idx].clone());
                 return true;
             }
-            Msg::ShowDeleteSkillPrompt => {
+            Msg::ShowArchiveSkillPrompt => {
+                self.displaying_modal = true;
+                self.modal_closed = false;
+                self.modal_title = "Warning".to_string();
+                self.modal_type = "warning".to_string();
+                self.modal_content = html! {
+                    <div>
+                    <h1>{"Are you sure?"}</h1>
+                    <p>{"Are you sure you'd like to move this skill to the trash?"}</p>
+                    <p>{"It will no longer be scheduled, but its history is kept and it can be restored from the "}<strong>{"Trash"}</strong>{" tab."}</p>
+                    <div>
+                        <div class="icon-text">
+                            <a title="Archive Skill" onclick={ctx.link().callback(|_| Msg::ArchiveSkill)}>
+                                <span class="icon is-medium">
+                                    <i class="fas fa-trash fa-lg"></i>
+                                </span>
+                            </a>
+                        </div>
+                        <a title="Archive Skill" onclick={ctx.link().callback(|_| Msg::ArchiveSkill)}>
+                        {"Archive Skill"}
+                        </a>
+                        </div>
+                    </div>
+                };
+            }
+            Msg::ShowPurgeSkillPrompt(skill_name) => {
                 // display a prompt because this is a pretty srs irreversible move
-
                 self.displaying_modal = true;
                 self.modal_closed = false;
                 self.modal_title = "Danger".to_string();
                 self.modal_type = "danger".to_string();
+                let purge_name = skill_name.clone();
                 self.modal_content = html! {
                     <div>
                     <h1>{"Are you sure?"}</h1>
-                    <p>{"Are you sure you'd like to delete this skill?"}</p>
-                    <p>{"This is "}<strong>{"irreversible"}</strong>{", and you will also delete any activities associated with this skill."}</p>
+                    <p>{"Are you sure you'd like to permanently delete "}<strong>{ skill_name.clone() }</strong>{"?"}</p>
+                    <p>{"This is "}<strong>{"irreversible"}</strong>{", and you will also delete any activities associated with this skill. Your practice history is kept."}</p>
                     <div>
                         <div class="icon-text">
-                            <a title="Delete Skill" onclick={ctx.link().callback(|_| Msg::DeleteSkill)}>
+                            <a title="Delete Skill" onclick={ctx.link().callback(move |_| Msg::PurgeSkill(skill_name.clone()))}>
                                 <span class="icon is-medium">
                                     <i class="fas fa-trash fa-lg"></i>
                                 </span>
                             </a>
                         </div>
-                        <a title="Delete Skill" onclick={ctx.link().callback(|_| Msg::DeleteSkill)}>
+                        <a title="Delete Skill" onclick={ctx.link().callback(move |_| Msg::PurgeSkill(purge_name.clone()))}>
                         {"Delete Skill"}
                         </a>
                         </div>
                     </div>
                 };
             }
-            Msg::DeleteSkill => {
+            Msg::ShowPurgeExercisePrompt(idx) => {
+                self.displaying_modal = true;
+                self.modal_closed = false;
+                self.modal_title = "Danger".to_string();
+                self.modal_type = "danger".to_string();
+                self.modal_content = html! {
+                    <div>
+                    <h1>{"Are you sure?"}</h1>
+                    <p>{"Are you sure you'd like to permanently delete this exercise?"}</p>
+                    <p>{"This is "}<strong>{"irreversible"}</strong>{"."}</p>
+                    <div>
+                        <div class="icon-text">
+                            <a title="Delete Exercise" onclick={ctx.link().callback(move |_| Msg::PurgeExercise(idx))}>
+                                <span class="icon is-medium">
+                                    <i class="fas fa-trash fa-lg"></i>
+                                </span>
+                            </a>
+                        </div>
+                        <a title="Delete Exercise" onclick={ctx.link().callback(move |_| Msg::PurgeExercise(idx))}>
+                        {"Delete Exercise"}
+                        </a>
+                        </div>
+                    </div>
+                };
+            }
+            Msg::SwitchProfile(name) => {
+                if self.scheduler.practicing || name == self.profiles.active_profile {
+                    return false;
+                }
+
+                self.save().expect("unable to save");
+                if let Err(e) = self.profiles.switch_profile(&name) {
+                    self.show_error(e);
+                    return true;
+                }
+                self.scheduler = load_scheduler(&name, ctx.link());
+                self.practice_minutes =
+                    self.scheduler.config.skill_practice_time.num_minutes() as usize;
+                self.selected_skill = None;
+                self.selected_exercise = None;
+                let current_time = get_current_time();
+                self.scheduler
+                    .update_todays_schedule(false, current_time)
+                    .expect("able to update schedule");
+                self.save().expect("unable to save");
+                self.offer_saved_session(ctx.link());
+            }
+            Msg::AddProfile => {
+                let window = web_sys::window().expect("no global `window` exists");
+                let document = window.document().expect("should have a document on window");
+                let profile_name_el = document
+                    .get_element_by_id("new_profile_name")
+                    .expect("should have a profile name element")
+                    .unchecked_into::<HtmlInputElement>();
+                let name = profile_name_el.value();
+
+                if let Err(e) = self.profiles.add_profile(&name) {
+                    self.show_error(e);
+                    return true;
+                }
+                profile_name_el.set_value("");
+                self.save().expect("unable to save");
+                ctx.link().send_message(Msg::SwitchProfile(name));
+            }
+            Msg::ShowDeleteProfilePrompt => {
+                self.displaying_modal = true;
+                self.modal_closed = false;
+                self.modal_title = "Danger".to_string();
+                self.modal_type = "danger".to_string();
+                self.modal_content = html! {
+                    <div>
+                    <h1>{"Are you sure?"}</h1>
+                    <p>{"Are you sure you'd like to delete the profile "}<strong>{ self.profiles.active_profile.clone() }</strong>{"?"}</p>
+                    <p>{"This is "}<strong>{"irreversible"}</strong>{", and will delete all of the history and settings for this profile."}</p>
+                    <div>
+                        <div class="icon-text">
+                            <a title="Delete Profile" onclick={ctx.link().callback(|_| Msg::DeleteProfile)}>
+                                <span class="icon is-medium">
+                                    <i class="fas fa-trash fa-lg"></i>
+                                </span>
+                            </a>
+                        </div>
+                        <a title="Delete Profile" onclick={ctx.link().callback(|_| Msg::DeleteProfile)}>
+                        {"Delete Profile"}
+                        </a>
+                        </div>
+                    </div>
+                };
+            }
+            Msg::DeleteProfile => {
+                if self.scheduler.practicing || self.profiles.is_default() {
+                    return false;
+                }
+
+                let deleted = self.profiles.active_profile.clone();
+                self.profiles
+                    .switch_profile(DEFAULT_PROFILE)
+                    .expect("default profile always exists");
+                if let Err(e) = self.profiles.remove_profile(&deleted) {
+                    self.show_error(e);
+                    return true;
+                }
+                LocalStorage::delete(profile_key(&deleted, CONFIG_KEY));
+                LocalStorage::delete(profile_key(&deleted, HISTORY_KEY));
+                LocalStorage::delete(profile_key(&deleted, SESSION_KEY));
+                LocalStorage::delete(profile_key(&deleted, TODAY_KEY));
+
+                self.scheduler = load_scheduler(DEFAULT_PROFILE, ctx.link());
+                self.practice_minutes =
+                    self.scheduler.config.skill_practice_time.num_minutes() as usize;
+                self.selected_skill = None;
+                self.selected_exercise = None;
+                let current_time = get_current_time();
+                self.scheduler
+                    .update_todays_schedule(false, current_time)
+                    .expect("able to update schedule");
+                self.save().expect("unable to save");
+
+                self.modal_closed = true;
+                self.displaying_modal = false;
+            }
+            Msg::UpdateSessionNote(note) => {
+                if let Some(ps) = self.scheduler.practice_session.as_mut() {
+                    let skill = ps.current_skill.clone();
+                    ps.set_note(&skill, &note);
+                }
+            }
+            Msg::UpdateHistoryNote(time, skill_name, note) => {
+                if let Err(e) = self.scheduler.set_history_note(&time, &skill_name, &note) {
+                    log::warn!("Unable to save note: {}", e);
+                    return false;
+                }
+                self.save().expect("unable to save");
+            }
+            Msg::SearchNotes(query) => {
+                self.notes_query = query;
+            }
+            Msg::LogPractice => {
+                let window = web_sys::window().expect("no global `window` exists");
+                let document = window.document().expect("should have a document on window");
+                let input_value = |id: &str| {
+                    document
+                        .get_element_by_id(id)
+                        .expect("should have a log practice element")
+                        .unchecked_into::<HtmlInputElement>()
+                        .value()
+                };
+
+                let now = get_current_time();
+                let date = input_value("log_date");
+                let time = match NaiveDate::parse_from_str(&date, "%Y-%m-%d") {
+                    Ok(date) if date != now.date().naive_utc() => {
+                        self.scheduler.past_log_time(date)
+                    }
+                    Ok(_) => now,
+                    Err(_) => {
+                        self.show_error("Choose the day you practiced");
+                        return true;
+                    }
+                };
+
+                let skill_names = self
+                    .scheduler
+                    .config
+                    .skills
+                    .iter()
+                    .map(|s| s.skill_name.clone())
+                    .collect::<Vec<_>>();
+                let mut skill_durations = vec![];
+                for (idx, skill_name) in skill_names.into_iter().enumerate() {
+                    let minutes = input_value(&format!("log_minutes_{}", idx));
+                    if minutes.trim().is_empty() {
+                        continue;
+                    }
+                    match minutes.trim().parse::<i64>() {
+                        Ok(0) => {}
+                        Ok(minutes) => {
+                            skill_durations.push((skill_name, Duration::minutes(minutes)))
+                        }
+                        Err(_) => {
+                            self.show_error(format!(
+                                "Minutes for {} must be a whole number",
+                                skill_name
+                            ));
+                            return true;
+                        }
+                    }
+                }
+
+                if let Err(e) = self.scheduler.log_practice(time, &skill_durations, now) {
+                    self.show_error(e);
+                    return true;
+                }
+                self.save().expect("unable to save");
+
+                for idx in 0..self.scheduler.config.skills.len() {
+                    document
+                        .get_element_by_id(&format!("log_minutes_{}", idx))
+                        .expect("should have a log practice element")
+                        .unchecked_into::<HtmlInputElement>()
+                        .set_value("");
+                }
+            }
+            Msg::ArchiveSkill => {
                 if self.selected_skill.is_none() {
-                    log::warn!("Tried deleting with nonexistent selected skill");
+                    log::warn!("Tried archiving with nonexistent selected skill");
                     return false;
                 }
 
                 // TODO should find a better identifier to pass between client/server
-                let skill = self.selected_skill.as_ref().unwrap().clone();
+                let skill = self.selected_skill.take().unwrap();
+                self.selected_exercise = None;
                 self.scheduler
-                    .delete_skill(skill)
-                    .expect("delete skill failure");
+                    .archive_skill(&skill.skill_name)
+                    .expect("archive skill failure");
+                if !self.scheduler.practicing {
+                    self.scheduler
+                        .update_todays_schedule(false, get_current_time())
+                        .expect("able to update schedule");
+                }
 
                 self.save().expect("unable to save");
                 self.modal_closed = true;
                 self.displaying_modal = false;
                 return true;
             }
+            Msg::ArchiveExercise => {
+                let (skill, exercise) = match (&self.selected_skill, &self.selected_exercise) {
+                    (Some(skill), Some(exercise)) => (skill.clone(), exercise.clone()),
+                    _ => {
+                        log::warn!("Tried archiving with nonexistent selected exercise");
+                        return false;
+                    }
+                };
+
+                self.scheduler
+                    .archive_exercise(&skill.skill_name, &exercise.exercise_name)
+                    .expect("archive exercise failure");
+                self.selected_skill = self.scheduler.get_skill(&skill.skill_name).ok();
+                self.selected_exercise = None;
+                self.save().expect("unable to save");
+            }
+            Msg::RestoreSkill(skill_name) => {
+                if let Err(e) = self.scheduler.restore_skill(&skill_name) {
+                    self.show_error(e);
+                    return true;
+                }
+                self.save().expect("unable to save");
+            }
+            Msg::PurgeSkill(skill_name) => {
+                if let Err(e) = self.scheduler.purge_skill(&skill_name) {
+                    self.show_error(e);
+                    return true;
+                }
+                self.save().expect("unable to save");
+                self.modal_closed = true;
+                self.displaying_modal = false;
+            }
+            Msg::RestoreExercise(idx) => {
+                if let Err(e) = self.scheduler.restore_exercise(idx) {
+                    self.show_error(e);
+                    return true;
+                }
+                self.selected_skill = None;
+                self.selected_exercise = None;
+                self.save().expect("unable to save");
+            }
+            Msg::PurgeExercise(idx) => {
+                if let Err(e) = self.scheduler.purge_exercise(idx) {
+                    self.show_error(e);
+                    return true;
+                }
+                self.save().expect("unable to save");
+                self.modal_closed = true;
+                self.displaying_modal = false;
+            }
+            Msg::ExportData => {
+                let now = get_current_time();
+                let json = match self.scheduler.export_json(now) {
+                    Ok(json) => json,
+                    Err(e) => {
+                        self.show_error(e);
+                        return true;
+                    }
+                };
+                let url = ObjectUrl::from(Blob::new_with_options(
+                    json.as_str(),
+                    Some("application/json"),
+                ));
+
+                // download the file through a temporary link
+                let window = web_sys::window().expect("no global `window` exists");
+                let document = window.document().expect("should have a document on window");
+                let link = document
+                    .create_element("a")
+                    .expect("able to create a link")
+                    .unchecked_into::<HtmlElement>();
+                link.set_attribute("href", &url).expect("able to set href");
+                link.set_attribute(
+                    "download",
+                    &format!(
+                        "practice-planner-{}-{}.json",
+                        self.profiles.active_profile,
+                        now.format("%Y-%m-%d")
+                    ),
+                )
+                .expect("able to set download");
+                link.click();
+                self.export_url = Some(url);
+                return false;
+            }
+            Msg::ImportFile(file) => {
+                if self.scheduler.practicing {
+                    return false;
+                }
+                if let Some(file) = file {
+                    let link = ctx.link().clone();
+                    self.import_reader = Some(read_as_text(&Blob::from(file), move |contents| {
+                        link.send_message(Msg::ImportData(contents.map_err(|e| e.to_string())))
+                    }));
+                }
+                return false;
+            }
+            Msg::ImportData(contents) => {
+                self.import_reader = None;
+                let added = match contents.map_err(anyhow::Error::msg).and_then(|json| {
+                    self.scheduler
+                        .import_json(&json)
+                        .map_err(anyhow::Error::from)
+                }) {
+                    Ok(added) => added,
+                    Err(e) => {
+                        self.show_error(e);
+                        return true;
+                    }
+                };
+                self.practice_minutes =
+                    self.scheduler.config.skill_practice_time.num_minutes() as usize;
+                self.selected_skill = None;
+                self.selected_exercise = None;
+                let current_time = get_current_time();
+                if let Err(e) = self.scheduler.update_todays_schedule(false, current_time) {
+                    self.show_error(e);
+                }
+                self.save().expect("unable to save");
+
+                self.displaying_modal = true;
+                self.modal_closed = false;
+                self.modal_title = "Info".to_string();
+                self.modal_type = "info".to_string();
+                self.modal_content = html! {
+                    <div>
+                    <p>{ format!("Imported settings and {} new practice sessions.", added) }</p>
+                    </div>
+                };
+            }
+            Msg::ResumeSavedSession => {
+                let saved = match self.saved_session.take() {
+                    Some(saved) => saved,
+                    None => return false,
+                };
+                self.modal_closed = true;
+                self.displaying_modal = false;
+                if let Err(e) = self.scheduler.restore_session(saved) {
+                    LocalStorage::delete(profile_key(&self.profiles.active_profile, SESSION_KEY));
+                    self.show_error(e);
+                    return true;
+                }
+                // the session picks up paused, so there's nothing to tick yet
+                self.last_completed_session = None;
+                self.show_current_exercise();
+            }
+            Msg::DiscardSavedSession => {
+                self.saved_session = None;
+                LocalStorage::delete(profile_key(&self.profiles.active_profile, SESSION_KEY));
+                self.modal_closed = true;
+                self.displaying_modal = false;
+            }
         }
 
+        // keeps the saved session up to date with whatever just changed
+        self.save_session(get_current_time());
         true
     }
 
@@ -801,7 +2025,7 @@ impl Component for PracticePlannerApp {
         // TODO use a constant here
         let history_list = self
             .scheduler
-            .get_history_n_days_back(3, current_time)
+            .sessions_n_days_back(3, current_time)
             .expect("unable to retrieve history");
 
         let cl = self.scheduler.config.skills
@@ -858,6 +2082,8 @@ impl Component for PracticePlannerApp {
                         // <p class="title">{ "Practice History" }</p>
                         {self.view_history_list(history_list, ctx.link())}
                         <p>{ "Streak: " }<strong>{ streak }{ " days" }</strong></p>
+                        {self.view_journal_notes(ctx.link())}
+                        {self.view_log_practice(ctx.link())}
                         <button class="favorite styled"
                                 type="button"
                                 onclick={ctx.link().callback(|_| Msg::ShowResetHistoryPrompt)}
@@ -865,6 +2091,9 @@ impl Component for PracticePlannerApp {
                             { "Reset History" }
                         </button>
                     } else if self.active_tab == 2 {
+                        {self.view_profile_settings(ctx.link())}
+                        {self.view_data_transfer(ctx.link())}
+
                         <p>
                         <label for="skill_list">{"Skills"}</label>
                         </p>
@@ -879,7 +2108,7 @@ impl Component for PracticePlannerApp {
                                 html! {
                                     <>
                                     <div class="icon-text">
-                                        <a title="Delete Skill" onclick={ctx.link().callback(|_| Msg::ShowDeleteSkillPrompt)}>
+                                        <a title="Archive Skill" onclick={ctx.link().callback(|_| Msg::ShowArchiveSkillPrompt)}>
                                             <span class="icon is-medium has-text-success">
                                                 <i class="fas fa-trash fa-lg"></i>
                                             </span>
@@ -896,6 +2125,13 @@ impl Component for PracticePlannerApp {
                                             if self.selected_exercise.is_some() {
                                                 // display an edit box for the markdown contents
                                                 html! {<>
+                                                    <div class="icon-text">
+                                                        <a title="Archive Exercise" onclick={ctx.link().callback(|_| Msg::ArchiveExercise)}>
+                                                            <span class="icon is-medium has-text-success">
+                                                                <i class="fas fa-trash fa-lg"></i>
+                                                            </span>
+                                                        </a>
+                                                    </div>
                                                     <textarea id="exercise_md_edit" value={self.selected_exercise.as_ref().unwrap().exercise_markdown_contents.clone()}>
                                                     </textarea>
 
@@ -918,6 +2154,54 @@ impl Component for PracticePlannerApp {
                         <p><label for="skill_count">{"Number of Skills to Practice Per Day"}</label></p>
                         <input id="skill_count" class="input is-primary" type="text" placeholder="4" value={format!("{}", self.scheduler.config.skills_per_day)} />
 
+                        {self.view_segment_settings(SegmentKind::WarmUp, "Warm-up, Practiced Before Every Session")}
+                        {self.view_segment_settings(SegmentKind::CoolDown, "Cool-down, Practiced After Every Session")}
+
+                        <p><label for="rest_minutes">{"Rest Break Minutes, 0 for No Breaks"}</label></p>
+                        <div class="field has-addons">
+                            <div class="control">
+                                <input id="rest_minutes" class="input is-primary" type="text" placeholder="0" value={format!("{}", self.scheduler.config.rest_breaks.as_ref().map_or(0, |r| r.rest_time.num_minutes()))} />
+                            </div>
+                            <div class="control">
+                                <label for="rest_every">{" after every "}</label>
+                            </div>
+                            <div class="control">
+                                <input id="rest_every" class="input is-primary" type="text" placeholder="1" value={format!("{}", self.scheduler.config.rest_breaks.as_ref().map_or(1, |r| r.every_skills))} />
+                            </div>
+                            <div class="control">
+                                <label for="rest_every">{" skills"}</label>
+                            </div>
+                        </div>
+
+                        <p><label for="interleave_rounds">{"Interleaved Rounds, 0 to Practice Each Skill in One Block"}</label></p>
+                        <div class="field has-addons">
+                            <div class="control">
+                                <input id="interleave_rounds" class="input is-primary" type="text" placeholder="0" value={format!("{}", self.scheduler.config.interleaving.as_ref().map_or(0, |i| i.rounds))} />
+                            </div>
+                            <div class="control">
+                                <label for="interleave_minutes">{" rounds of "}</label>
+                            </div>
+                            <div class="control">
+                                <input id="interleave_minutes" class="input is-primary" type="text" placeholder="5" value={format!("{}", self.scheduler.config.interleaving.as_ref().map_or(5, |i| i.block_time.num_minutes()))} />
+                            </div>
+                            <div class="control">
+                                <label for="interleave_minutes">{" minutes per skill"}</label>
+                            </div>
+                        </div>
+
+                        <p>
+                            <label class="checkbox" for="overtime">
+                                <input id="overtime" type="checkbox" checked={self.scheduler.config.overtime} />
+                                {" Keep the timer running past zero until I move on"}
+                            </label>
+                        </p>
+                        <p>
+                            <label class="checkbox" for="exclude_practiced_today">
+                                <input id="exclude_practiced_today" type="checkbox" checked={self.scheduler.config.exclude_practiced_today} />
+                                {" Leave out skills already practiced today from later sessions"}
+                            </label>
+                        </p>
+
                         <button class="favorite styled"
                                 type="button"
                                 onclick={ctx.link().callback(|_| Msg::ShowResetSettingsPrompt)}
@@ -931,6 +2215,8 @@ impl Component for PracticePlannerApp {
                                 >
                                 {"Save Changes"}
                         </button>
+                    } else if self.active_tab == 3 {
+                        {self.view_trash(ctx.link())}
                     }
                     </div>
                     // this should only show on the practice tab
@@ -938,10 +2224,14 @@ impl Component for PracticePlannerApp {
 
                     <div class="tile is-child content app-panel">
                         {if self.scheduler.practicing {
-
-                            self.rendered_exercise.clone()
+                            html! {
+                                <>
+                                { self.rendered_exercise.clone() }
+                                { self.view_session_notes(ctx.link()) }
+                                </>
+                            }
                         } else {
-                            html! {<></>}
+                            self.view_completed_session_notes(ctx.link())
                         }
                         }
 